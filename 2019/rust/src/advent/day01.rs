@@ -1,8 +1,10 @@
 use crate::utils::errors::Error;
-use crate::utils::files::{problem_input_path, read_file_split_whitespace};
+use aoc_core::parse::{parse_lines, ParseError};
+
+type Modules = Vec<f32>;
 
 fn fuel_cost(weight: f32) -> u32 {
-    ((weight / 3.0) as u32).checked_sub(2).unwrap_or(0)
+    ((weight / 3.0) as u32).saturating_sub(2)
 }
 
 fn iterative_cost(weight: f32) -> u32 {
@@ -15,16 +17,21 @@ fn iterative_cost(weight: f32) -> u32 {
     cost
 }
 
-pub fn part_one() -> Result<u32, Error> {
-    let input_path = problem_input_path(1, None);
-    let numbers = read_file_split_whitespace(&input_path)?;
-    let sum = numbers.into_iter().map(fuel_cost).sum();
+pub fn parse(input: &str) -> Result<Modules, Error> {
+    let modules = parse_lines(input, |line| {
+        line.trim()
+            .parse()
+            .map_err(|_| ParseError::at(line, 0, format!("bad module mass {line:?}")))
+    })?;
+    Ok(modules)
+}
+
+pub fn part_one(numbers: &Modules) -> Result<u32, Error> {
+    let sum = numbers.iter().copied().map(fuel_cost).sum();
     Ok(sum)
 }
 
-pub fn part_two() -> Result<u32, Error> {
-    let input_path = problem_input_path(1, None);
-    let numbers = read_file_split_whitespace(&input_path)?;
-    let sum = numbers.into_iter().map(iterative_cost).sum();
+pub fn part_two(numbers: &Modules) -> Result<u32, Error> {
+    let sum = numbers.iter().copied().map(iterative_cost).sum();
     Ok(sum)
 }
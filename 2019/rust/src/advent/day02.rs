@@ -1,10 +1,15 @@
 use crate::utils::errors::Error;
-use crate::utils::files::{problem_input_path, read_file_split_on};
 use crate::utils::tape_machine::{TapeMachine, TapeMachineState};
+use aoc_core::parse::parse_separated;
 
-pub fn part_one() -> Result<i32, Error> {
-    let input_path = problem_input_path(2, None);
-    let mut tape = read_file_split_on(&input_path, ",")?;
+type Program = Vec<i32>;
+
+pub fn parse(input: &str) -> Result<Program, Error> {
+    Ok(parse_separated(input, ",")?)
+}
+
+pub fn part_one(program: &Program) -> Result<i32, Error> {
+    let mut tape = program.clone();
     tape[1] = 12;
     tape[2] = 2;
     let mut tape_machine = TapeMachine::new(tape, false);
@@ -14,9 +19,7 @@ pub fn part_one() -> Result<i32, Error> {
     }
 }
 
-pub fn part_two() -> Result<i32, Error> {
-    let input_path = problem_input_path(2, None);
-    let orig_tape = read_file_split_on(&input_path, ",")?;
+pub fn part_two(orig_tape: &Program) -> Result<i32, Error> {
     for noun in 0..=99 {
         for verb in 0..=99 {
             let mut tape = orig_tape.clone();
@@ -28,10 +31,21 @@ pub fn part_two() -> Result<i32, Error> {
                     if tape_machine.get_value(0) == 19690720 {
                         return Ok(100 * noun + verb);
                     }
-                },
+                }
                 _ => return Err(Error::NoSolutionFound),
             }
         }
     }
     Err(Error::NoSolutionFound)
 }
+
+#[test]
+fn points_at_bad_input() {
+    match parse("1,0,0,3,99,x\n") {
+        Err(Error::Parse(error)) => {
+            assert_eq!((error.line, error.column), (1, 12));
+            assert_eq!(error.text, "1,0,0,3,99,x");
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
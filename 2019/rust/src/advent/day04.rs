@@ -1,5 +1,11 @@
 use crate::utils::errors::Error;
-use crate::utils::files::{problem_input_path, read_file_split_on};
+use aoc_core::scan;
+
+type Range = (u32, u32);
+
+pub fn parse(input: &str) -> Result<Range, Error> {
+    Ok(scan!(input.trim_end(), "{min}-{max}" => u32, u32)?)
+}
 
 fn is_valid_part_one(candidate: u32) -> bool {
     let digits: Vec<u32> = candidate
@@ -24,10 +30,8 @@ fn is_valid_part_one(candidate: u32) -> bool {
     has_adjacent && monotonic
 }
 
-pub fn part_one() -> Result<u32, Error> {
-    let input_path = problem_input_path(4, None);
-    let range = read_file_split_on(&input_path, "-")?;
-    let (min, max) = (range[0], range[1]);
+pub fn part_one(range: &Range) -> Result<u32, Error> {
+    let &(min, max) = range;
     let mut count = 0;
     for candidate in min..max {
         if is_valid_part_one(candidate) {
@@ -67,10 +71,8 @@ fn is_valid_part_two(candidate: u32) -> bool {
     has_adjacent && monotonic
 }
 
-pub fn part_two() -> Result<u32, Error> {
-    let input_path = problem_input_path(4, None);
-    let range = read_file_split_on(&input_path, "-")?;
-    let (min, max) = (range[0], range[1]);
+pub fn part_two(range: &Range) -> Result<u32, Error> {
+    let &(min, max) = range;
     let mut count = 0;
     for candidate in min..max {
         if is_valid_part_two(candidate) {
@@ -1,11 +1,15 @@
 use crate::utils::errors::Error;
-use crate::utils::files::{problem_input_path, read_file_split_on};
 use crate::utils::tape_machine::{TapeMachine, TapeMachineState};
+use aoc_core::parse::parse_separated;
 
-pub fn part_one() -> Result<i32, Error> {
-    let input_path = problem_input_path(5, None);
-    let tape = read_file_split_on(&input_path, ",")?;
-    let mut tape_machine = TapeMachine::new(tape, false);
+type Program = Vec<i32>;
+
+pub fn parse(input: &str) -> Result<Program, Error> {
+    Ok(parse_separated(input, ",")?)
+}
+
+pub fn part_one(program: &Program) -> Result<i32, Error> {
+    let mut tape_machine = TapeMachine::new(program.clone(), false);
     tape_machine.add_input(1);
     match tape_machine.run()? {
         TapeMachineState::Halted => {
@@ -21,10 +25,8 @@ pub fn part_one() -> Result<i32, Error> {
     }
 }
 
-pub fn part_two() -> Result<i32, Error> {
-    let input_path = problem_input_path(5, None);
-    let tape = read_file_split_on(&input_path, ",")?;
-    let mut tape_machine = TapeMachine::new(tape, false);
+pub fn part_two(program: &Program) -> Result<i32, Error> {
+    let mut tape_machine = TapeMachine::new(program.clone(), false);
     tape_machine.add_input(5);
     match tape_machine.run()? {
         TapeMachineState::Halted => {
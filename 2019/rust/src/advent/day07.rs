@@ -1,6 +1,6 @@
 use crate::utils::errors::Error;
-use crate::utils::files::{problem_input_path, read_file_split_on};
 use crate::utils::tape_machine::{TapeMachine, TapeMachineState};
+use aoc_core::parse::parse_separated;
 
 use std::collections::VecDeque;
 
@@ -12,13 +12,9 @@ fn _generate(k: usize, source: &mut Vec<i32>) -> Vec<Vec<i32>> {
         results.append(&mut _generate(k - 1, source));
         for i in 0..(k - 1) {
             if k & 1 == 0 {
-                let tmp = source[i];
-                source[i] = source[k - 1];
-                source[k - 1] = tmp;
+                source.swap(i, k - 1);
             } else {
-                let tmp = source[0];
-                source[0] = source[k - 1];
-                source[k - 1] = tmp;
+                source.swap(0, k - 1);
             }
             results.append(&mut _generate(k - 1, source));
         }
@@ -29,15 +25,19 @@ fn _generate(k: usize, source: &mut Vec<i32>) -> Vec<Vec<i32>> {
 /// Implementation of Heap's algorithm to generate all permutations
 /// I'd like to turn this into an iterator, but the recursive implementation is more
 /// easily understood.
-fn permutations(source: &Vec<i32>) -> Vec<Vec<i32>> {
-    _generate(source.len(), &mut source.clone())
+fn permutations(source: &[i32]) -> Vec<Vec<i32>> {
+    _generate(source.len(), &mut source.to_vec())
 }
 
-pub fn part_one() -> Result<i32, Error> {
-    let input_path = problem_input_path(7, None);
-    let tape = read_file_split_on(&input_path, ",")?;
-    let mut highest_signal = i32::min_value();
-    for state in permutations(&vec![0, 1, 2, 3, 4]) {
+type Program = Vec<i32>;
+
+pub fn parse(input: &str) -> Result<Program, Error> {
+    Ok(parse_separated(input, ",")?)
+}
+
+pub fn part_one(tape: &Program) -> Result<i32, Error> {
+    let mut highest_signal = i32::MIN;
+    for state in permutations(&[0, 1, 2, 3, 4]) {
         let mut previous_signal = 0;
         for amp_phase in state {
             let mut tape_machine = TapeMachine::new(tape.clone(), false);
@@ -58,12 +58,10 @@ pub fn part_one() -> Result<i32, Error> {
     Ok(highest_signal)
 }
 
-pub fn part_two() -> Result<i32, Error> {
-    let input_path = problem_input_path(7, None);
-    let tape = read_file_split_on(&input_path, ",")?;
+pub fn part_two(tape: &Program) -> Result<i32, Error> {
     let mut amps = VecDeque::new();
-    let mut highest_signal = i32::min_value();
-    for phase_states in permutations(&vec![5, 6, 7, 8, 9]) {
+    let mut highest_signal = i32::MIN;
+    for phase_states in permutations(&[5, 6, 7, 8, 9]) {
         for phase in phase_states {
             let mut tape_machine = TapeMachine::new(tape.clone(), true);
             tape_machine.add_input(phase);
@@ -1,8 +1,12 @@
 use crate::utils::errors::Error;
-use crate::utils::files::problem_input_path;
 
+use aoc_core::parse::ParseError;
 use std::collections::VecDeque;
-use std::fs;
+
+const IMAGE_WIDTH: usize = 25;
+const IMAGE_HEIGHT: usize = 6;
+
+type Image = Vec<u32>;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Pixel {
@@ -50,16 +54,21 @@ fn to_layers<T>(mut v: Vec<T>, layer_size: usize) -> Vec<Vec<T>> {
     layers.into()
 }
 
-pub fn part_one() -> Result<u32, Error> {
-    let input_path = problem_input_path(8, None);
-    let img: Vec<u32> = fs::read_to_string(&input_path)?
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .collect();
-    let image_width = 25;
-    let image_height = 6;
-    let layer_size = image_height * image_width;
-    let layers = to_layers(img, layer_size);
+pub fn parse(input: &str) -> Result<Image, Error> {
+    let input = input.trim_end();
+    let image = input
+        .char_indices()
+        .map(|(offset, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::at(input, offset, format!("bad pixel {c:?}")))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(image)
+}
+
+pub fn part_one(img: &Image) -> Result<u32, Error> {
+    let layer_size = IMAGE_HEIGHT * IMAGE_WIDTH;
+    let layers = to_layers(img.clone(), layer_size);
     if let Some(min_layer) = layers
         .iter()
         .min_by_key(|layer| layer.iter().filter(|&&pixel| pixel == 0).count())
@@ -79,16 +88,9 @@ pub fn part_one() -> Result<u32, Error> {
     }
 }
 
-pub fn part_two() -> Result<u32, Error> {
-    let input_path = problem_input_path(8, None);
-    let pixels: Vec<Pixel> = fs::read_to_string(&input_path)?
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .map(|d| d.into())
-        .collect();
-    let image_width = 25;
-    let image_height = 6;
-    let layer_size = image_height * image_width;
+pub fn part_two(img: &Image) -> Result<String, Error> {
+    let pixels: Vec<Pixel> = img.iter().map(|&d| d.into()).collect();
+    let layer_size = IMAGE_HEIGHT * IMAGE_WIDTH;
     let layers = to_layers(pixels, layer_size);
     let mut rasterized_pixels = Vec::new();
     for idx in 0..layer_size {
@@ -101,12 +103,12 @@ pub fn part_two() -> Result<u32, Error> {
         }
         rasterized_pixels.push(pixel)
     }
+    let mut raster = String::new();
     for (pixel, i) in rasterized_pixels.into_iter().zip(0..layer_size) {
-        if i % image_width == 0 {
-            println!("");
+        if i % IMAGE_WIDTH == 0 {
+            raster.push('\n');
         }
-        print!("{}", pixel.raster());
+        raster.push(pixel.raster());
     }
-    println!("");
-    Ok(0)
+    Ok(raster)
 }
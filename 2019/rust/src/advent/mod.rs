@@ -5,4 +5,4 @@ pub mod day04;
 pub mod day05;
 pub mod day06;
 pub mod day07;
-pub mod day08;
\ No newline at end of file
+pub mod day08;
@@ -0,0 +1,46 @@
+pub mod advent;
+mod utils;
+
+use aoc_core::{not_implemented, run};
+use eyre::Result;
+
+const YEAR: u16 = 2019;
+
+/// Solves one part of one day against its puzzle input.
+pub fn solve(day: u8, part: u8) -> Result<()> {
+    match (day, part) {
+        // Fuel Required: 3576689
+        (1, 1) => run(YEAR, 1, advent::day01::parse, advent::day01::part_one),
+        // Fuel Required: 5362136
+        (1, 2) => run(YEAR, 1, advent::day01::parse, advent::day01::part_two),
+        // Gravity Assist: 9581917
+        (2, 1) => run(YEAR, 2, advent::day02::parse, advent::day02::part_one),
+        // Gravity Assist: 2505
+        (2, 2) => run(YEAR, 2, advent::day02::parse, advent::day02::part_two),
+        // Crossed Wires: 709
+        (3, 1) => run(YEAR, 3, advent::day03::parse, advent::day03::part_one),
+        // Wire Length: 13836
+        (3, 2) => run(YEAR, 3, advent::day03::parse, advent::day03::part_two),
+        // Secure Container: 2090
+        (4, 1) => run(YEAR, 4, advent::day04::parse, advent::day04::part_one),
+        // Secure Container Fewer Dupes: 1419
+        (4, 2) => run(YEAR, 4, advent::day04::parse, advent::day04::part_two),
+        // Emulator Enhancement: 16434972
+        (5, 1) => run(YEAR, 5, advent::day05::parse, advent::day05::part_one),
+        // Emulator Enhancement: 16694270
+        (5, 2) => run(YEAR, 5, advent::day05::parse, advent::day05::part_two),
+        // Orbits Dont Work This Way: 278744
+        (6, 1) => run(YEAR, 6, advent::day06::parse, advent::day06::part_one),
+        // I am a Steely-eyed Missile Man: 475
+        (6, 2) => run(YEAR, 6, advent::day06::parse, advent::day06::part_two),
+        // Maximizing Warp Drives: 199988
+        (7, 1) => run(YEAR, 7, advent::day07::parse, advent::day07::part_one),
+        // Resumable Tape Machine: 17519904
+        (7, 2) => run(YEAR, 7, advent::day07::parse, advent::day07::part_two),
+        // Space Selfies: 1360
+        (8, 1) => run(YEAR, 8, advent::day08::parse, advent::day08::part_one),
+        // Space Memes: FPUAR
+        (8, 2) => run(YEAR, 8, advent::day08::parse, advent::day08::part_two),
+        (d, p) => not_implemented(YEAR, d, p),
+    }
+}
@@ -1,21 +1,47 @@
+use aoc_core::parse::ParseError;
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum Error {
     IoError(std::io::Error),
     ParseIntError(std::num::ParseIntError),
     ParseFloatError(std::num::ParseFloatError),
-    DirectionParseError(String),
     NoSolutionFound,
     Infallible,
     BadOpcode(String),
     TryFromIntError(std::num::TryFromIntError),
+    Parse(ParseError),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IoError(err) => write!(f, "io error: {}", err),
+            Self::ParseIntError(err) => write!(f, "failed to parse integer: {}", err),
+            Self::ParseFloatError(err) => write!(f, "failed to parse float: {}", err),
+            Self::NoSolutionFound => write!(f, "no solution found"),
+            Self::Infallible => write!(f, "infallible"),
+            Self::BadOpcode(op) => write!(f, "bad opcode [{}]", op),
+            Self::TryFromIntError(err) => write!(f, "integer conversion failed: {}", err),
+            Self::Parse(err) => write!(f, "{}", err),
+        }
+    }
 }
 
+impl std::error::Error for Error {}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
         Self::IoError(err)
     }
 }
 
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        Self::Parse(err)
+    }
+}
+
 impl From<std::num::TryFromIntError> for Error {
     fn from(err: std::num::TryFromIntError) -> Error {
         Self::TryFromIntError(err)
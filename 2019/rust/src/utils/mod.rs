@@ -1,3 +1,2 @@
 pub mod errors;
-pub mod files;
-pub mod tape_machine;
\ No newline at end of file
+pub mod tape_machine;
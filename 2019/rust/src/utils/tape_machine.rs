@@ -44,7 +44,7 @@ enum OpCode {
 impl OpCode {
     fn decode(tape: &Tape, ip: Address) -> Result<OpCode, Error> {
         let mut instruction = tape[ip].to_string();
-        let split_loc = instruction.len().checked_sub(2).unwrap_or(0);
+        let split_loc = instruction.len().saturating_sub(2);
         let opcode = format!("{:0>2}", instruction.split_off(split_loc));
         let modes: Vec<Address> = instruction
             .chars()
@@ -54,33 +54,33 @@ impl OpCode {
             .collect();
         match opcode.as_ref() {
             "01" => Ok(OpCode::Add(
-                param(&tape, ip + 1, modes.get(0))?,
-                param(&tape, ip + 2, modes.get(1))?,
+                param(tape, ip + 1, modes.first())?,
+                param(tape, ip + 2, modes.get(1))?,
                 usize::try_from(tape[ip + 3])?,
             )),
             "02" => Ok(OpCode::Mul(
-                param(&tape, ip + 1, modes.get(0))?,
-                param(&tape, ip + 2, modes.get(1))?,
+                param(tape, ip + 1, modes.first())?,
+                param(tape, ip + 2, modes.get(1))?,
                 usize::try_from(tape[ip + 3])?,
             )),
             "03" => Ok(OpCode::Input(usize::try_from(tape[ip + 1])?)),
-            "04" => Ok(OpCode::Output(param(&tape, ip + 1, modes.get(0))?)),
+            "04" => Ok(OpCode::Output(param(tape, ip + 1, modes.first())?)),
             "05" => Ok(OpCode::JumpIfTrue(
-                param(&tape, ip + 1, modes.get(0))?,
-                param(&tape, ip + 2, modes.get(1))?,
+                param(tape, ip + 1, modes.first())?,
+                param(tape, ip + 2, modes.get(1))?,
             )),
             "06" => Ok(OpCode::JumpIfFalse(
-                param(&tape, ip + 1, modes.get(0))?,
-                param(&tape, ip + 2, modes.get(1))?,
+                param(tape, ip + 1, modes.first())?,
+                param(tape, ip + 2, modes.get(1))?,
             )),
             "07" => Ok(OpCode::LessThan(
-                param(&tape, ip + 1, modes.get(0))?,
-                param(&tape, ip + 2, modes.get(1))?,
+                param(tape, ip + 1, modes.first())?,
+                param(tape, ip + 2, modes.get(1))?,
                 usize::try_from(tape[ip + 3])?,
             )),
             "08" => Ok(OpCode::Equal(
-                param(&tape, ip + 1, modes.get(0))?,
-                param(&tape, ip + 2, modes.get(1))?,
+                param(tape, ip + 1, modes.first())?,
+                param(tape, ip + 2, modes.get(1))?,
                 usize::try_from(tape[ip + 3])?,
             )),
             "99" => Ok(OpCode::Halt),
@@ -106,11 +106,11 @@ pub struct TapeMachine {
 impl TapeMachine {
     pub fn new(tape: Tape, yield_on_input: bool) -> Self {
         TapeMachine {
-            tape: tape,
+            tape,
             ip: 0,
             input: VecDeque::new(),
             output: VecDeque::new(),
-            yield_on_input: yield_on_input,
+            yield_on_input,
         }
     }
     fn execute_opcode(&mut self, op: OpCode) -> Result<Option<TapeMachineState>, Error> {
@@ -192,4 +192,4 @@ impl TapeMachine {
     pub fn add_input(&mut self, val: Value) {
         self.input.push_back(val)
     }
-}
\ No newline at end of file
+}
@@ -18,7 +18,7 @@ func recursive_cost(weight: Float) -> Int {
     return cost
 }
 
-let path = "../advent_problems/day01/input"
+let path = "../problems/day01/input1"
 
 if let handle = FileHandle.init(forReadingAtPath: path) {
     let inputString = String(data: handle.readDataToEndOfFile(), encoding: String.Encoding.utf8)!
@@ -0,0 +1,215 @@
+//! The day 16 decoder from before the bit reader, copied as it was so the benchmark measures
+//! what it replaced. It expands each hex digit into a queue of `bool`s through `format!` and
+//! reads every field by collecting bits back into a number. Only the visibility has changed.
+
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Literal {
+    version: u64,
+    ty: u64,
+    value: u64,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Operator {
+    version: u64,
+    ty: u64,
+    subpackets: Vec<Packet>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Packet {
+    Lit(Literal),
+    Op(Operator),
+}
+
+#[derive(Debug)]
+pub struct BitStream {
+    buffer: VecDeque<bool>,
+    characters: VecDeque<char>,
+}
+
+impl BitStream {
+    fn is_empty(&self) -> bool {
+        self.buffer.is_empty() && self.characters.is_empty()
+    }
+    // fn clear_buffer(&mut self) {
+    //     self.buffer.clear();
+    // }
+}
+
+impl From<Vec<bool>> for BitStream {
+    fn from(v: Vec<bool>) -> Self {
+        BitStream {
+            buffer: v.into(),
+            characters: Default::default(),
+        }
+    }
+}
+
+impl Iterator for BitStream {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(bit) = self.buffer.pop_front() {
+            Some(bit)
+        } else if let Some(c) = self.characters.pop_front() {
+            let bits = c.to_digit(16).unwrap();
+            // println!("{} = '{}'", c, format!("{:04b}", bits));
+            for c in format!("{:04b}", bits).chars() {
+                // println!("'{}'", c);
+                self.buffer.push_back(match c {
+                    '0' => false,
+                    '1' => true,
+                    _ => unreachable!(),
+                });
+            }
+            self.buffer.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+pub fn parse_line(line: &str) -> BitStream {
+    BitStream {
+        buffer: Default::default(),
+        characters: line.chars().collect(),
+    }
+}
+
+fn bools_to_bits(bools: impl Iterator<Item = bool>) -> u64 {
+    let bools: Vec<bool> = bools.collect();
+    bools
+        .into_iter()
+        .rev()
+        .enumerate()
+        .map(|(idx, b)| (b as u64) << idx)
+        .sum()
+}
+
+pub fn parse_stream(stream: &mut BitStream) -> Packet {
+    // println!("entering new parsing with {:?}", stream);
+    let version = bools_to_bits(stream.by_ref().take(3));
+    let ty = bools_to_bits(stream.by_ref().take(3));
+    match ty {
+        4 => {
+            let mut bools = Vec::new();
+            // let mut taken = 6;
+            while let Some(b) = stream.next() {
+                // taken += 5;
+                if b {
+                    bools.extend(stream.by_ref().take(4));
+                } else {
+                    bools.extend(stream.by_ref().take(4));
+                    break;
+                }
+            }
+            let value = bools_to_bits(bools.into_iter());
+            // let alignment = taken % 4;
+            // println!("{}, {:?}", alignment, stream);
+            // if alignment != 0 {
+            //     let _drop: Vec<bool> = stream.by_ref().take(4 - alignment).collect();
+            // }
+            // println!("{:?}", stream);
+            Packet::Lit(Literal { version, ty, value })
+        }
+        _ => {
+            let mut subpackets = Vec::new();
+            if stream.next().unwrap() {
+                let number_of_subpackets = bools_to_bits(stream.by_ref().take(11));
+                for _ in 0..number_of_subpackets {
+                    subpackets.push(parse_stream(stream));
+                }
+            } else {
+                let number_of_bits = bools_to_bits(stream.by_ref().take(15)) as usize;
+                let bits: Vec<bool> = stream.by_ref().take(number_of_bits).collect();
+                let mut bits: BitStream = bits.into();
+                while !bits.is_empty() {
+                    subpackets.push(parse_stream(&mut bits));
+                }
+            }
+            Packet::Op(Operator {
+                version,
+                ty,
+                subpackets,
+            })
+        }
+    }
+}
+
+pub fn version_sum(packet: &Packet) -> u64 {
+    match packet {
+        Packet::Lit(literal) => literal.version,
+        Packet::Op(operator) => {
+            let subpacket_sum: u64 = operator.subpackets.iter().map(version_sum).sum();
+            operator.version + subpacket_sum
+        }
+    }
+}
+
+pub fn evaluate(packet: &Packet) -> u64 {
+    match packet {
+        Packet::Lit(literal) => literal.value,
+        Packet::Op(operator) => {
+            match operator.ty {
+                0 => {
+                    // sum
+                    operator.subpackets.iter().map(evaluate).sum()
+                }
+                1 => {
+                    // product
+                    operator.subpackets.iter().map(evaluate).product()
+                }
+                2 => {
+                    // minimum
+                    operator.subpackets.iter().map(evaluate).min().unwrap()
+                }
+                3 => {
+                    // max
+                    operator.subpackets.iter().map(evaluate).max().unwrap()
+                }
+                5 => {
+                    // greater than
+                    if let [first, second] = &*operator.subpackets {
+                        if evaluate(first) > evaluate(second) {
+                            1
+                        } else {
+                            0
+                        }
+                    } else {
+                        panic!("bad pattern match for gt")
+                    }
+                }
+                6 => {
+                    // less than
+                    if let [first, second] = &*operator.subpackets {
+                        if evaluate(first) < evaluate(second) {
+                            1
+                        } else {
+                            0
+                        }
+                    } else {
+                        panic!("bad pattern match for gt")
+                    }
+                }
+                7 => {
+                    // equal to
+                    if let [first, second] = &*operator.subpackets {
+                        if evaluate(first) == evaluate(second) {
+                            1
+                        } else {
+                            0
+                        }
+                    } else {
+                        panic!("bad pattern match for gt")
+                    }
+                }
+                _ => panic!("ahhh!"),
+            }
+        }
+    }
+}
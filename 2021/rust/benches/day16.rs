@@ -0,0 +1,28 @@
+use aoc2021::advent::day16;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+mod bitstream;
+
+fn decode_bitstream(input: &str) -> bitstream::Packet {
+    bitstream::parse_stream(&mut bitstream::parse_line(input.trim()))
+}
+
+/// Decoding the puzzle input, which is almost all of the work for the day, with the bit
+/// reader and with the `VecDeque<bool>` stream it replaced.
+fn decode(c: &mut Criterion) {
+    let input = aoc_core::read_input(2021, 16).expect("the puzzle input is needed to benchmark");
+    // both decoders have to agree for the comparison to mean anything
+    let old = decode_bitstream(&input);
+    let new = day16::parse(&input).expect("the bit reader should decode the input");
+    assert_eq!(bitstream::version_sum(&old), day16::part_one(&new).unwrap());
+    assert_eq!(bitstream::evaluate(&old), day16::part_two(&new).unwrap());
+    let mut group = c.benchmark_group("day16 decode");
+    group.bench_function("bitstream", |b| {
+        b.iter(|| decode_bitstream(black_box(&input)))
+    });
+    group.bench_function("bit reader", |b| b.iter(|| day16::parse(black_box(&input))));
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -1,22 +1,29 @@
 use crate::utils::*;
+use aoc_core::parse::parse_lines;
 
-pub fn part_one() {
-    let ip = problem_input_path(1, Some(1));
-    let data: Vec<i64> = read_file_split_whitespace(&ip).expect("failed to parse");
-    let increase_count = data
+type Depths = Vec<i64>;
+
+pub fn parse(input: &str) -> Result<Depths> {
+    Ok(parse_lines(input, |line| line.trim().parse())?)
+}
+
+pub fn part_one(data: &Depths) -> Result<usize> {
+    Ok(data
         .iter()
         .zip(data.iter().skip(1))
         .filter(|(a, b)| a < b)
-        .count();
-    println!("{}", increase_count);
+        .count())
 }
 
-pub fn part_two() {
-    // reuses the same input
-    let ip = problem_input_path(1, Some(1));
-    let data: Vec<i64> = read_file_split_whitespace(&ip).expect("failed to parse");
+pub fn part_two(data: &Depths) -> Result<usize> {
     let window_sums = data.windows(3).map(|w| w.iter().sum());
     let zip = window_sums.clone().zip(window_sums.skip(1));
-    let increase_count = zip.filter(|(a, b): &(i64, i64)| a < b).count();
-    println!("{}", increase_count)
+    Ok(zip.filter(|(a, b): &(i64, i64)| a < b).count())
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(1).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 1583);
+    assert_eq!(part_two(&input).unwrap(), 1627);
 }
@@ -1,49 +1,59 @@
 use crate::utils::*;
+use aoc_core::parse::parse_lines;
+use eyre::{ContextCompat, Report};
+use std::str::FromStr;
 
-enum Heading {
+pub enum Heading {
     Forward(i64),
     Down(i64),
     Up(i64),
 }
 
-impl From<String> for Heading {
-    fn from(s: String) -> Self {
+impl FromStr for Heading {
+    type Err = Report;
+    fn from_str(s: &str) -> Result<Self> {
         let mut splitter = s.split_whitespace();
-        let direction = splitter.next().expect("expected direction");
+        let direction = splitter.next().wrap_err("expected direction")?;
         let magnitude = splitter
             .next()
-            .expect(&format!("expected magnitude for {}", s))
-            .parse::<i64>()
-            .expect("failed to parse magnitude");
+            .wrap_err_with(|| format!("expected magnitude for {}", s))?
+            .parse::<i64>()?;
         match direction {
-            "forward" => Self::Forward(magnitude),
-            "down" => Self::Down(magnitude),
-            "up" => Self::Up(magnitude),
-            _ => panic!("Malformed input"),
+            "forward" => Ok(Self::Forward(magnitude)),
+            "down" => Ok(Self::Down(magnitude)),
+            "up" => Ok(Self::Up(magnitude)),
+            _ => Err(Report::msg(format!("unknown direction {}", direction))),
         }
     }
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(2, Some(1));
-    let data: Vec<String> = read_file_split_on(&ip, "\n").expect("failed to parse");
-    let parsed: Vec<Heading> = data.into_iter().map(From::from).collect();
-    let (x_pos, depth) = parsed.into_iter().fold((0, 0), |(x, d), h| match h {
+type Course = Vec<Heading>;
+
+pub fn parse(input: &str) -> Result<Course> {
+    Ok(parse_lines(input, str::parse)?)
+}
+
+pub fn part_one(course: &Course) -> Result<i64> {
+    let (x_pos, depth) = course.iter().fold((0, 0), |(x, d), h| match h {
         Heading::Forward(m) => (x + m, d),
         Heading::Down(m) => (x, d + m),
         Heading::Up(m) => (x, d - m),
     });
-    println!("{} x {} = {}", x_pos, depth, x_pos * depth);
+    Ok(x_pos * depth)
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(2, Some(1));
-    let data: Vec<String> = read_file_split_on(&ip, "\n").expect("failed to parse");
-    let parsed: Vec<Heading> = data.into_iter().map(From::from).collect();
-    let (x_pos, depth, _) = parsed.into_iter().fold((0, 0, 0), |(x, d, a), h| match h {
+pub fn part_two(course: &Course) -> Result<i64> {
+    let (x_pos, depth, _) = course.iter().fold((0, 0, 0), |(x, d, a), h| match h {
         Heading::Forward(m) => (x + m, d + a * m, a),
         Heading::Down(m) => (x, d, a + m),
         Heading::Up(m) => (x, d, a - m),
     });
-    println!("{} x {} = {}", x_pos, depth, x_pos * depth);
+    Ok(x_pos * depth)
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(2).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 2102357);
+    assert_eq!(part_two(&input).unwrap(), 2101031224);
 }
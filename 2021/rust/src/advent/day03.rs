@@ -1,20 +1,21 @@
 use crate::utils::*;
+use eyre::ContextCompat;
 
-fn gamma_digits_from_count(counts: &Vec<u32>, threshold: u32) -> Vec<u32> {
+fn gamma_digits_from_count(counts: &[u32], threshold: u32) -> Vec<u32> {
     counts
         .iter()
-        .map(|&count| if count >= threshold { 1 } else { 0 })
+        .map(|&count| u32::from(count >= threshold))
         .collect()
 }
 
-fn epsilon_digits_from_count(counts: &Vec<u32>, threshold: u32) -> Vec<u32> {
+fn epsilon_digits_from_count(counts: &[u32], threshold: u32) -> Vec<u32> {
     counts
         .iter()
-        .map(|&count| if count >= threshold { 0 } else { 1 })
+        .map(|&count| u32::from(count < threshold))
         .collect()
 }
 
-fn number_from_digits(digits: &Vec<u32>) -> u32 {
+fn number_from_digits(digits: &[u32]) -> u32 {
     digits
         .iter()
         .rev()
@@ -22,69 +23,67 @@ fn number_from_digits(digits: &Vec<u32>) -> u32 {
         .fold(0, |accum, (idx, num)| accum + (num << idx))
 }
 
-fn occurence_counter(data: &Vec<String>) -> Vec<u32> {
-    data.into_iter()
+fn occurence_counter(data: &[String]) -> Result<Vec<u32>> {
+    data.iter()
         .map(|s| {
             s.chars()
-                .map(|c| c.to_digit(2).unwrap())
-                .collect::<Vec<u32>>()
+                .map(|c| c.to_digit(2).wrap_err_with(|| format!("bad digit {}", c)))
+                .collect::<Result<Vec<u32>>>()
         })
-        .reduce(|v1, v2| v1.into_iter().zip(v2).map(|(a, b)| a + b).collect())
-        .unwrap()
+        .reduce(|v1, v2| Ok(v1?.into_iter().zip(v2?).map(|(a, b)| a + b).collect()))
+        .wrap_err("no diagnostics to count")?
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(3, Some(1));
-    let data: Vec<String> = read_file_split_on(&ip, "\n").expect("failed to parse");
+type Diagnostics = Vec<String>;
+
+pub fn parse(input: &str) -> Result<Diagnostics> {
+    split_on(input, "\n")
+}
+
+pub fn part_one(data: &Diagnostics) -> Result<u32> {
     let threshold = (data.len() / 2) as u32;
-    let counts = occurence_counter(&data);
+    let counts = occurence_counter(data)?;
     let gamma_digits = gamma_digits_from_count(&counts, threshold);
     let epsilon_digits = epsilon_digits_from_count(&counts, threshold);
     let gamma = number_from_digits(&gamma_digits);
     let epsilon = number_from_digits(&epsilon_digits);
-    println!(
-        "gamma = {}, epsilon = {}, power = {}",
-        gamma,
-        epsilon,
-        gamma * epsilon
-    );
+    Ok(gamma * epsilon)
 }
 
-fn find_rating(mut candidates: Vec<String>, digitizer: impl Fn(&Vec<u32>, u32) -> Vec<u32>) -> u32 {
+fn find_rating(
+    mut candidates: Vec<String>,
+    digitizer: impl Fn(&[u32], u32) -> Vec<u32>,
+) -> Result<u32> {
     let mut idx = 0;
     while candidates.len() > 1 {
         // when odd, round up
         // e.g., when there are 7 items, 3.5 => 4 so that 4 items are needed to be the most common
         let threshold = (candidates.len() as f32 / 2.).ceil() as u32;
-        let counts = occurence_counter(&candidates);
+        let counts = occurence_counter(&candidates)?;
         let gamma_digits = digitizer(&counts, threshold);
-        let digit_char = gamma_digits
+        let digit = *gamma_digits
             .get(idx)
-            .unwrap()
-            .to_string()
-            .chars()
-            .next()
-            .unwrap();
-        candidates = candidates
-            .into_iter()
-            .filter(|s| s.chars().skip(idx).next().unwrap() == digit_char)
-            .collect();
+            .wrap_err_with(|| format!("ran out of digits at index {}", idx))?;
+        let digit_char = char::from_digit(digit, 2).wrap_err("digit out of range")?;
+        candidates.retain(|s| s.chars().nth(idx) == Some(digit_char));
         idx += 1;
     }
-    u32::from_str_radix(candidates.get(0).unwrap(), 2).unwrap()
+    let rating = candidates.first().wrap_err("no candidates remain")?;
+    Ok(u32::from_str_radix(rating, 2)?)
+}
+
+pub fn part_two(data: &Diagnostics) -> Result<u32> {
+    let oxygen_generator_candidates = data.clone();
+    let co2_scrubber_candidates = data.clone();
+    let oxygen_generator_rating =
+        find_rating(oxygen_generator_candidates, gamma_digits_from_count)?;
+    let co2_scrubber_rating = find_rating(co2_scrubber_candidates, epsilon_digits_from_count)?;
+    Ok(oxygen_generator_rating * co2_scrubber_rating)
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(3, Some(1));
-    let oxygen_generator_candidates: Vec<String> =
-        read_file_split_on(&ip, "\n").expect("failed to parse");
-    let co2_scrubber_candidates = oxygen_generator_candidates.clone();
-    let oxygen_generator_rating = find_rating(oxygen_generator_candidates, gamma_digits_from_count);
-    let co2_scrubber_rating = find_rating(co2_scrubber_candidates, epsilon_digits_from_count);
-    println!(
-        "oxygen generator rating = {}, co2 scrubber rating = {}, life support rating = {}",
-        oxygen_generator_rating,
-        co2_scrubber_rating,
-        oxygen_generator_rating * co2_scrubber_rating
-    );
+#[test]
+fn answers() {
+    let input = parse(&read_input(3).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 2003336);
+    assert_eq!(part_two(&input).unwrap(), 1877139);
 }
@@ -1,5 +1,6 @@
 use crate::utils::*;
-use itertools::Itertools;
+use aoc_core::parse::{parse_blocks, parse_lines};
+use eyre::{ContextCompat, Report};
 
 type BingoBoard = Vec<(usize, bool)>;
 
@@ -41,60 +42,71 @@ fn mark(board: &mut BingoBoard, draw: usize) -> BoardResult {
     result
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(4, Some(1));
-    let data: Vec<String> = read_file_split_whitespace(&ip).expect("failed to parse");
-    let (numbers, boards) = data.split_at(1);
-    let numbers: Result<Vec<usize>, _> = numbers[0].split(",").map(str::parse::<usize>).collect();
-    let numbers = numbers.unwrap();
-    let mut boards: Vec<BingoBoard> = boards
-        .iter()
-        .map(|s| s.parse::<usize>().unwrap())
-        .chunks(25)
-        .into_iter()
-        .map(|chunk| chunk.map(|num| (num, false)).collect::<BingoBoard>())
-        .collect();
-    for draw in numbers {
+pub struct Bingo {
+    numbers: Vec<usize>,
+    boards: Vec<BingoBoard>,
+}
+
+pub fn parse(input: &str) -> Result<Bingo> {
+    // the drawn numbers come first, and then each board, all separated by blank lines
+    let mut blocks = parse_blocks(input, |block| {
+        parse_lines(block, |line| {
+            line.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|number| !number.is_empty())
+                .map(str::parse::<usize>)
+                .collect::<std::result::Result<Vec<_>, _>>()
+        })
+    })?
+    .into_iter();
+    let numbers = blocks.next().wrap_err("no numbers to draw")?.concat();
+    let boards = blocks
+        .enumerate()
+        .map(|(index, rows)| {
+            if rows.len() != 5 || rows.iter().any(|row| row.len() != 5) {
+                return Err(Report::msg(format!("board {} is not 5 by 5", index + 1)));
+            }
+            Ok(rows.concat().into_iter().map(|num| (num, false)).collect())
+        })
+        .collect::<Result<_>>()?;
+    Ok(Bingo { numbers, boards })
+}
+
+pub fn part_one(bingo: &Bingo) -> Result<usize> {
+    let mut boards = bingo.boards.clone();
+    for &draw in bingo.numbers.iter() {
         for board in boards.iter_mut() {
-            match mark(board, draw) {
-                BoardResult::Win(score) => {
-                    println!("Score of first win: {}", score);
-                    return;
-                }
-                _ => {}
+            if let BoardResult::Win(score) = mark(board, draw) {
+                return Ok(score);
             }
         }
     }
+    Err(Report::msg("no board ever won"))
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(4, Some(1));
-    let data: Vec<String> = read_file_split_whitespace(&ip).expect("failed to parse");
-    let (numbers, boards) = data.split_at(1);
-    let numbers: Result<Vec<usize>, _> = numbers[0].split(",").map(str::parse::<usize>).collect();
-    let numbers = numbers.unwrap();
-    let mut boards: Vec<(BingoBoard, bool)> = boards
+pub fn part_two(bingo: &Bingo) -> Result<usize> {
+    let mut boards: Vec<(BingoBoard, bool)> = bingo
+        .boards
         .iter()
-        .map(|s| s.parse::<usize>().unwrap())
-        .chunks(25)
-        .into_iter()
-        .map(|chunk| (chunk.map(|num| (num, false)).collect::<BingoBoard>(), false))
+        .map(|board| (board.clone(), false))
         .collect();
     let mut score = 0;
-    for draw in numbers {
-        for idx in 0..boards.len() {
-            let (board, already_won) = &mut boards[idx];
+    for &draw in bingo.numbers.iter() {
+        for (board, already_won) in boards.iter_mut() {
             if *already_won {
                 continue;
             }
-            match mark(board, draw) {
-                BoardResult::Win(s) => {
-                    *already_won = true;
-                    score = s;
-                }
-                _ => {}
+            if let BoardResult::Win(s) = mark(board, draw) {
+                *already_won = true;
+                score = s;
             }
         }
     }
-    println!("Score of last win: {}", score);
+    Ok(score)
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(4).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 38913);
+    assert_eq!(part_two(&input).unwrap(), 16836);
 }
@@ -1,8 +1,12 @@
 use crate::utils::*;
+use aoc_core::parse::parse_separated;
+use eyre::Report;
+
+type School = Vec<usize>;
 
 // rotate_left would have made this so much easier
 fn simulate(populations: &mut [u128; 9]) {
-    let previous = populations.clone();
+    let previous = *populations;
     for (idx, count) in previous.into_iter().enumerate().rev() {
         if idx == 0 {
             populations[6] += count;
@@ -13,46 +17,58 @@ fn simulate(populations: &mut [u128; 9]) {
     }
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(6, Some(1));
-    let fishes: Vec<usize> = read_file_split_on(&ip, ",").unwrap();
+fn populations(fishes: &School) -> [u128; 9] {
     let mut populations: [u128; 9] = [0; 9];
-    for fish in fishes.into_iter() {
+    for &fish in fishes.iter() {
         populations[fish] += 1;
     }
-    for _day in 0..80 {
+    populations
+}
+
+fn simulate_days(fishes: &School, days: usize) -> u128 {
+    let mut populations = populations(fishes);
+    for _day in 0..days {
         simulate(&mut populations);
     }
-    println!("number of fish: {}", populations.into_iter().sum::<u128>());
+    populations.into_iter().sum()
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(6, Some(1));
-    let fishes: Vec<usize> = read_file_split_on(&ip, ",").unwrap();
-    let mut populations: [u128; 9] = [0; 9];
-    for fish in fishes.into_iter() {
-        populations[fish] += 1;
+pub fn parse(input: &str) -> Result<School> {
+    let fishes: School = parse_separated(input, ",")?;
+    match fishes.iter().find(|&&fish| fish > 8) {
+        Some(fish) => Err(Report::msg(format!("fish timer {} is out of range", fish))),
+        None => Ok(fishes),
     }
-    for _day in 0..256 {
-        simulate(&mut populations);
-    }
-    println!("number of fish: {}", populations.into_iter().sum::<u128>());
 }
 
+pub fn part_one(fishes: &School) -> Result<u128> {
+    Ok(simulate_days(fishes, 80))
+}
+
+pub fn part_two(fishes: &School) -> Result<u128> {
+    Ok(simulate_days(fishes, 256))
+}
+
+aoc_core::example_tests!(crate::YEAR, 6);
+
 #[test]
 fn example() {
-    let fishes = vec![3, 4, 3, 1, 2];
-    let mut populations: [u128; 9] = [0; 9];
-    for fish in fishes.into_iter() {
-        populations[fish] += 1;
-    }
+    let fishes = parse(&read_example(6, 1).unwrap()).unwrap();
+    let mut populations = populations(&fishes);
     for _day in 0..18 {
-        println!("{:?}", populations);
         simulate(&mut populations);
     }
     assert_eq!(populations.iter().sum::<u128>(), 26);
-    for _day in 0..(80 - 18) {
-        simulate(&mut populations);
-    }
-    assert_eq!(populations.iter().sum::<u128>(), 5934);
+
+    let error = parse("3,4,3,1,x\n").unwrap_err();
+    let error = error.downcast::<aoc_core::parse::ParseError>().unwrap();
+    assert_eq!((error.line, error.column), (1, 9));
+    assert!(parse("3,4,9\n").is_err());
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(6).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 352195);
+    assert_eq!(part_two(&input).unwrap(), 1600306001288);
 }
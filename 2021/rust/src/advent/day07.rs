@@ -1,27 +1,34 @@
 use crate::utils::*;
+use aoc_core::parse::parse_separated;
+use eyre::Report;
+
+type Positions = Vec<i32>;
 
 fn nth_sum(n: i32) -> i32 {
-    (n * (n+1)) / 2
+    (n * (n + 1)) / 2
 }
 
-fn solve_median_linear(mut positions: Vec<i32>) -> i32 {
+fn solve_median_linear(mut positions: Positions) -> i32 {
     positions.sort_unstable();
     let length = positions.len();
     let median = if length & 1 == 1 {
-        positions[length/2]
+        positions[length / 2]
     } else {
-        let middle = length/2;
-        (positions[middle-1] + positions[middle])/2
+        let middle = length / 2;
+        (positions[middle - 1] + positions[middle]) / 2
     };
-    positions.into_iter().map(|p| (p-median).abs()).sum()
+    positions.into_iter().map(|p| (p - median).abs()).sum()
 }
 
-fn brute_force(mut positions: Vec<i32>) -> i32 {
+fn brute_force(mut positions: Positions) -> i32 {
     positions.sort_unstable();
     let mut _best = 0;
     let mut cost = 1 << 30; // big number, not relevant
-    for candidate_position in positions[0]..positions[positions.len()-1] {
-        let candidate_cost: i32 = positions.iter().map(|p| nth_sum((*p-candidate_position).abs())).sum();
+    for candidate_position in positions[0]..positions[positions.len() - 1] {
+        let candidate_cost: i32 = positions
+            .iter()
+            .map(|p| nth_sum((*p - candidate_position).abs()))
+            .sum();
         if candidate_cost < cost {
             _best = candidate_position;
             cost = candidate_cost;
@@ -30,21 +37,27 @@ fn brute_force(mut positions: Vec<i32>) -> i32 {
     cost
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(7, Some(1));
-    let positions: Vec<i32> = read_file_split_on(&ip, ",").unwrap();
-    println!("fuel needed: {}", solve_median_linear(positions));
+pub fn parse(input: &str) -> Result<Positions> {
+    let positions: Positions = parse_separated(input, ",")?;
+    if positions.is_empty() {
+        return Err(Report::msg("no crab positions"));
+    }
+    Ok(positions)
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(7, Some(1));
-    let positions: Vec<i32> = read_file_split_on(&ip, ",").unwrap();
-    println!("fuel needed: {}", brute_force(positions));
+pub fn part_one(positions: &Positions) -> Result<i32> {
+    Ok(solve_median_linear(positions.clone()))
 }
 
+pub fn part_two(positions: &Positions) -> Result<i32> {
+    Ok(brute_force(positions.clone()))
+}
+
+aoc_core::example_tests!(crate::YEAR, 7);
+
 #[test]
-fn example() {
-    let positions = vec![16,1,2,0,4,2,7,1,2,14];
-    assert_eq!(solve_median_linear(positions.clone()), 37);
-    assert_eq!(brute_force(positions), 168);
+fn answers() {
+    let input = parse(&read_input(7).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 353800);
+    assert_eq!(part_two(&input).unwrap(), 98119739);
 }
@@ -1,108 +1,356 @@
-use std::collections::{BTreeSet, HashMap};
-use itertools::Itertools;
+use eyre::{ContextCompat, WrapErr};
 
 use crate::utils::*;
+use aoc_core::parse::parse_lines;
 
 // 1, 4, 7, 8 in 7-segment displays use a unique number of digits
 const UNIQUE_COUNTS: [usize; 4] = [2, 4, 3, 7];
 
-pub fn part_one() {
-    let ip = problem_input_path(8, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let unambiguous_count: usize = lines
-        .into_iter()
-        .map(|line| {
-            line.split_whitespace()
-                .skip_while(|s| *s != "|")
-                .skip(1) // skip the |
-                .map(str::len)
+pub struct Entry {
+    patterns: Vec<String>,
+    output: Vec<String>,
+}
+
+type Notes = Vec<Entry>;
+
+fn parse_entry(line: &str) -> Result<Entry> {
+    let (patterns, output) = line
+        .split_once('|')
+        .wrap_err_with(|| format!("missing delimiter in {}", line))?;
+    Ok(Entry {
+        patterns: patterns.split_whitespace().map(String::from).collect(),
+        output: output.split_whitespace().map(String::from).collect(),
+    })
+}
+
+pub fn parse(input: &str) -> Result<Notes> {
+    Ok(parse_lines(input, parse_entry)?)
+}
+
+pub fn part_one(notes: &Notes) -> Result<usize> {
+    Ok(notes
+        .iter()
+        .map(|entry| {
+            entry
+                .output
+                .iter()
+                .map(String::len)
                 .filter(|l| UNIQUE_COUNTS.contains(l))
                 .count()
         })
-        .sum();
-    println!("unambiguous characters: {}", unambiguous_count);
+        .sum())
 }
 
-fn solve(input: &[&str]) -> HashMap<BTreeSet<char>, char> {
-    let mut sets: Vec<BTreeSet<char>> = input
+/// The segments each digit lights, as bits from `a` (bit 0) to `g` (bit 6).
+const DIGITS: [u8; 10] = [
+    0b1110111, // 0: abcefg
+    0b0100100, // 1: cf
+    0b1011101, // 2: acdeg
+    0b1101101, // 3: acdfg
+    0b0101110, // 4: bcdf
+    0b1101011, // 5: abdfg
+    0b1111011, // 6: abdefg
+    0b0100101, // 7: acf
+    0b1111111, // 8: abcdefg
+    0b1101111, // 9: abcdfg
+];
+
+const ALL_SEGMENTS: u8 = 0b1111111;
+
+/// Why the wires of a display could not be worked out, or an output could not be read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WiringError {
+    /// A pattern that is not a set of distinct wires from `a` to `g`.
+    BadPattern(String),
+    /// No wiring lights every pattern as a digit.
+    Contradiction,
+    /// More than one wiring lights every pattern as a digit, and these wires do not go to the
+    /// same segment in all of them.
+    Ambiguous(Vec<char>),
+    /// A pattern that lights no digit with this wiring.
+    NotADigit(String),
+}
+
+impl std::fmt::Display for WiringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WiringError::BadPattern(pattern) => {
+                write!(f, "{pattern:?} is not a set of wires from a to g")
+            }
+            WiringError::Contradiction => write!(f, "no wiring lights every pattern as a digit"),
+            WiringError::Ambiguous(wires) => write!(
+                f,
+                "more than one wiring fits, with wires {} going to different segments",
+                wires.iter().collect::<String>()
+            ),
+            WiringError::NotADigit(pattern) => write!(f, "{pattern:?} lights no digit"),
+        }
+    }
+}
+
+impl std::error::Error for WiringError {}
+
+/// The wires in a pattern, as bits from `a` (bit 0) to `g` (bit 6).
+fn wires(pattern: &str) -> Result<u8, WiringError> {
+    let mut wires = 0u8;
+    for c in pattern.chars() {
+        let bit = match c {
+            'a'..='g' => 1 << (c as u8 - b'a'),
+            _ => return Err(WiringError::BadPattern(pattern.to_string())),
+        };
+        if wires & bit != 0 {
+            return Err(WiringError::BadPattern(pattern.to_string()));
+        }
+        wires |= bit;
+    }
+    Ok(wires)
+}
+
+fn bits(set: u8) -> impl Iterator<Item = usize> {
+    (0..7).filter(move |bit| set & (1 << bit) != 0)
+}
+
+/// The segments each wire could still go to.
+type Candidates = [u8; 7];
+
+/// Narrows the candidates until nothing changes, returning false if some wire is left with no
+/// segment it could go to.
+///
+/// Each pattern must light a digit with as many segments, and one whose segments its wires can
+/// reach while the other wires can reach the rest. So a wire in the pattern can only go to a
+/// segment of one of those digits, and a wire outside it only to a segment one of them leaves
+/// dark. Then, as each wire goes to a different segment, a wire with one candidate left takes it
+/// from the others, and a segment only one wire could go to is that wire's.
+fn propagate(candidates: &mut Candidates, patterns: &[u8]) -> bool {
+    loop {
+        let before = *candidates;
+        for &pattern in patterns {
+            let (mut inside, mut outside) = (0, 0);
+            for digit in DIGITS {
+                let fits = digit.count_ones() == pattern.count_ones()
+                    && bits(ALL_SEGMENTS).all(|wire| {
+                        let lit = if pattern & (1 << wire) != 0 {
+                            digit
+                        } else {
+                            !digit & ALL_SEGMENTS
+                        };
+                        candidates[wire] & lit != 0
+                    })
+                    && bits(pattern).fold(0, |reached, wire| reached | candidates[wire]) & digit
+                        == digit;
+                if fits {
+                    inside |= digit;
+                    outside |= !digit & ALL_SEGMENTS;
+                }
+            }
+            for (wire, candidate) in candidates.iter_mut().enumerate() {
+                *candidate &= if pattern & (1 << wire) != 0 {
+                    inside
+                } else {
+                    outside
+                };
+            }
+        }
+        for wire in 0..7 {
+            if candidates[wire].count_ones() == 1 {
+                for other in (0..7).filter(|&other| other != wire) {
+                    candidates[other] &= !candidates[wire];
+                }
+            }
+        }
+        for segment in 0..7 {
+            let bit = 1 << segment;
+            let mut able = (0..7).filter(|&wire| candidates[wire] & bit != 0);
+            if let (Some(wire), None) = (able.next(), able.next()) {
+                candidates[wire] = bit;
+            }
+        }
+        if candidates.contains(&0) {
+            return false;
+        }
+        if *candidates == before {
+            return true;
+        }
+    }
+}
+
+/// Collects every wiring that fits the patterns, propagating and then trying each candidate for
+/// the least certain wire in turn. There are at most 5040, for no patterns at all.
+fn search(mut candidates: Candidates, patterns: &[u8], found: &mut Vec<Candidates>) {
+    if !propagate(&mut candidates, patterns) {
+        return;
+    }
+    let undecided = (0..7)
+        .filter(|&wire| candidates[wire].count_ones() > 1)
+        .min_by_key(|&wire| candidates[wire].count_ones());
+    let Some(wire) = undecided else {
+        found.push(candidates);
+        return;
+    };
+    for segment in bits(candidates[wire]) {
+        let mut guess = candidates;
+        guess[wire] = 1 << segment;
+        search(guess, patterns, found);
+    }
+}
+
+/// Which segment each of a display's scrambled wires goes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wiring {
+    /// The segment for each wire, from `a` to `g`.
+    segments: [u8; 7],
+}
+
+impl Wiring {
+    /// Works out the wiring from patterns that each light some digit, which need not be all ten.
+    pub fn deduce(patterns: &[String]) -> Result<Wiring, WiringError> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| wires(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut found = Vec::new();
+        search([ALL_SEGMENTS; 7], &patterns, &mut found);
+        let wiring = |candidates: &Candidates| Wiring {
+            segments: candidates.map(|segment| segment.trailing_zeros() as u8),
+        };
+        match &found[..] {
+            [] => Err(WiringError::Contradiction),
+            [only] => Ok(wiring(only)),
+            [first, rest @ ..] => Err(WiringError::Ambiguous(
+                (0..7)
+                    .filter(|&wire| rest.iter().any(|other| other[wire] != first[wire]))
+                    .map(|wire| (b'a' + wire as u8) as char)
+                    .collect(),
+            )),
+        }
+    }
+
+    /// The segments wires `a` to `g` go to, in that order.
+    pub fn permutation(&self) -> String {
+        self.segments
+            .iter()
+            .map(|&segment| (b'a' + segment) as char)
+            .collect()
+    }
+
+    /// The digit a pattern lights.
+    pub fn digit(&self, pattern: &str) -> Result<u8, WiringError> {
+        let lit = bits(wires(pattern)?).fold(0, |lit, wire| lit | 1 << self.segments[wire]);
+        DIGITS
+            .iter()
+            .position(|&digit| digit == lit)
+            .map(|digit| digit as u8)
+            .ok_or_else(|| WiringError::NotADigit(pattern.to_string()))
+    }
+
+    /// The number a display's digits show, most significant first.
+    pub fn read(&self, output: &[String]) -> Result<u64, WiringError> {
+        output.iter().try_fold(0, |number, pattern| {
+            Ok(number * 10 + self.digit(pattern)? as u64)
+        })
+    }
+}
+
+/// The number each entry's display shows, deducing its wiring from its own patterns.
+pub fn decode_all(notes: &Notes) -> Result<Vec<u64>> {
+    notes
         .iter()
-        .map(|s| s.chars().collect::<BTreeSet<char>>())
-        .collect();
-    let one = sets.remove(sets.iter().position(|h| h.len() == 2).unwrap());
-    let seven = sets.remove(sets.iter().position(|h| h.len() == 3).unwrap());
-    let four = sets.remove(sets.iter().position(|h| h.len() == 4).unwrap());
-    let eight = sets.remove(sets.iter().position(|h| h.len() == 7).unwrap());
-    let nine = sets.remove(
-        sets.iter()
-            .position(|h| h.len() == 6 && h & &four == four)
-            .unwrap(),
+        .enumerate()
+        .map(|(index, entry)| {
+            Wiring::deduce(&entry.patterns)
+                .and_then(|wiring| wiring.read(&entry.output))
+                .wrap_err_with(|| format!("entry {}", index + 1))
+        })
+        .collect()
+}
+
+pub fn part_two(notes: &Notes) -> Result<u64> {
+    Ok(decode_all(notes)?.iter().sum())
+}
+
+aoc_core::example_tests!(crate::YEAR, 8);
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(8).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 390);
+    assert_eq!(part_two(&input).unwrap(), 1011785);
+}
+
+#[cfg(test)]
+fn strings(patterns: &str) -> Vec<String> {
+    patterns.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn example() {
+    let notes = parse(&read_example(8, 1).unwrap()).unwrap();
+    let wiring = Wiring::deduce(&notes[0].patterns).unwrap();
+    // the statement's wiring takes d to the top segment, e to the top left and so on
+    assert_eq!(wiring.permutation(), "cfgabde");
+    assert_eq!(wiring.read(&notes[0].output).unwrap(), 5353);
+    assert_eq!(decode_all(&notes).unwrap(), vec![5353]);
+}
+
+#[test]
+fn reports_unsolvable_wirings() {
+    // a one alone says nothing of where the other five wires go
+    assert_eq!(
+        Wiring::deduce(&strings("ab")),
+        Err(WiringError::Ambiguous(vec![
+            'a', 'b', 'c', 'd', 'e', 'f', 'g'
+        ]))
     );
-    let zero = sets.remove(
-        sets.iter()
-            .position(|h| h.len() == 6 && (h - &one).len() == 4)
-            .unwrap(),
+    // two different ones
+    assert_eq!(
+        Wiring::deduce(&strings("ab cd")),
+        Err(WiringError::Contradiction)
     );
-    // nine and zero have been removed, leaving only six
-    let six = sets.remove(sets.iter().position(|h| h.len() == 6).unwrap());
-    let five = sets.remove(
-        sets.iter()
-            .position(|h| h.len() == 5 && (h - &six).is_empty())
-            .unwrap(),
+    assert_eq!(
+        Wiring::deduce(&strings("ab xy")),
+        Err(WiringError::BadPattern("xy".to_string()))
     );
-    let three = sets.remove(
-        sets.iter()
-            .position(|h| h.len() == 5 && (h - &one).len() == 3)
-            .unwrap(),
+    let wiring = Wiring::deduce(&strings(
+        "abcefg cf acdeg acdfg bcdf abdfg abdefg acf abcdefg abcdfg",
+    ))
+    .unwrap();
+    assert_eq!(wiring.permutation(), "abcdefg");
+    assert_eq!(
+        wiring.digit("ab"),
+        Err(WiringError::NotADigit("ab".to_string()))
     );
-    // five and three have been removed, leaving only two
-    let two = sets.remove(sets.iter().position(|h| h.len() == 5).unwrap());
-    HashMap::from_iter(
-        [zero, one, two, three, four, five, six, seven, eight, nine]
-            .into_iter()
-            .enumerate()
-            .map(|(i, v)| (v, i.to_string().chars().next().unwrap())),
-    )
-}
-
-pub fn part_two() {
-    let ip = problem_input_path(8, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let mut sum: usize = 0;
-    for line in lines {
-        let items: Vec<&str> = line.split_whitespace().collect();
-        let mut split_by_delimiter = items.split(|s| *s == "|");
-        let input = split_by_delimiter.next().unwrap();
-        let output = split_by_delimiter.next().unwrap();
-        let permutation = solve(input);
-        let number = output
+}
+
+/// Some of the ten digits, shuffled and lit through a random wiring, along with the segment each
+/// wire goes to.
+#[cfg(test)]
+fn scrambled_digits() -> impl proptest::strategy::Strategy<Value = (Vec<String>, Vec<u8>)> {
+    use proptest::prelude::*;
+    let segments = Just((0..7).collect::<Vec<u8>>()).prop_shuffle();
+    let digits =
+        proptest::sample::subsequence((0..10).collect::<Vec<usize>>(), 1..=10).prop_shuffle();
+    (segments, digits).prop_map(|(segments, digits)| {
+        let patterns = digits
             .into_iter()
-            .map(|s| {
-                permutation
-                    .get(&s.chars().collect::<BTreeSet<char>>())
-                    .unwrap()
+            .map(|digit| {
+                (0..7)
+                    .filter(|&wire| DIGITS[digit] & (1 << segments[wire]) != 0)
+                    .map(|wire| (b'a' + wire as u8) as char)
+                    .collect()
             })
-            .join("");
-        sum += number.parse::<usize>().unwrap();
-    }
-    println!("sum {}", sum);
+            .collect();
+        (patterns, segments)
+    })
 }
 
-#[test]
-fn example() {
-    let example = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab |
-    cdfeb fcadb cdfeb cdbaf";
-    let items: Vec<&str> = example.split_whitespace().collect();
-    let mut split_by_delimiter = items.split(|s| *s == "|");
-    let input = split_by_delimiter.next().unwrap();
-    let output = split_by_delimiter.next().unwrap();
-    let permutation = solve(input);
-    let number = output
-        .into_iter()
-        .map(|s| {
-            permutation
-                .get(&s.chars().collect::<BTreeSet<char>>())
-                .unwrap()
-        })
-        .join("");
-    assert_eq!(number, "5353");
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn deduces_only_the_true_wiring((patterns, segments) in scrambled_digits()) {
+        match Wiring::deduce(&patterns) {
+            Ok(wiring) => proptest::prop_assert_eq!(&wiring.segments[..], &segments[..]),
+            Err(WiringError::Ambiguous(_)) => proptest::prop_assert!(patterns.len() < 10),
+            Err(error) => proptest::prop_assert!(false, "{}", error),
+        }
+    }
 }
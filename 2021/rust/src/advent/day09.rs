@@ -1,174 +1,108 @@
-use std::collections::BTreeSet;
-
 use crate::utils::*;
+use aoc_core::grid::{Grid, Position};
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::region::Regions;
 
-struct Neighbors {
-    items: Vec<(i32, i32)>,
-}
-
-impl Neighbors {
-    fn iter(row: i32, row_max: i32, col: i32, col_max: i32) -> Neighbors {
-        let mut items = Vec::new();
-        if row - 1 >= 0 {
-            items.push((row - 1, col));
-        }
-        if col - 1 >= 0 {
-            items.push((row, col - 1));
-        }
-        if row + 1 < row_max {
-            items.push((row + 1, col));
-        }
-        if col + 1 < col_max {
-            items.push((row, col + 1));
-        }
-        Neighbors { items }
-    }
-}
+type Height = u8;
+type Floor = Grid<Height>;
 
-impl Iterator for Neighbors {
-    type Item = (i32, i32);
+/// The highest locations, which belong to no basin.
+const PEAK: Height = 9;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.items.is_empty() {
-            None
-        } else {
-            self.items.pop()
-        }
-    }
+/// Locations lower than all of their neighbors.
+pub fn low_points(floor: &Floor) -> impl Iterator<Item = Position> + '_ {
+    floor.positions().filter(|&position| {
+        floor
+            .neighbors4(position)
+            .all(|neighbor| floor[position] < floor[neighbor])
+    })
 }
 
-fn risk_level(floor: Vec<Vec<i32>>) -> i32 {
-    let rows = floor.len();
-    let columns = floor.get(0).unwrap().len();
-    let mut low_points = Vec::new();
-    for row_idx in 0..rows {
-        for col_idx in 0..columns {
-            let value = floor[row_idx][col_idx];
-            let mut pass = true;
-            for (nrow, ncol) in
-                Neighbors::iter(row_idx as i32, rows as i32, col_idx as i32, columns as i32)
-            {
-                if value >= floor[nrow as usize][ncol as usize] {
-                    pass = false;
-                }
-            }
-            if pass {
-                low_points.push(value + 1);
-            }
-        }
-    }
-    low_points.iter().sum()
+/// The regions of the floor walled in by peaks.
+pub fn basins(floor: &Floor) -> Regions {
+    Regions::find(floor, |&height| height == PEAK)
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(9, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let floor: Vec<Vec<i32>> = lines
-        .into_iter()
-        .map(|s| {
-            s.chars()
-                .map(|c| c.to_string().parse::<i32>().unwrap())
-                .collect()
-        })
-        .collect();
-    println!("{}", risk_level(floor));
+/// The floor with each basin in its own color.
+pub fn render_basins(floor: &Floor) -> String {
+    basins(floor).render(floor, |&height| char::from(b'0' + height))
 }
 
-// TODO: why is this so slow? need to profile...
-pub fn part_two() {
-    let start = std::time::Instant::now();
-    let ip = problem_input_path(9, Some(1));
-    println!("{:?}", std::time::Instant::now() - start);
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let floor: Vec<Vec<i32>> = lines
-        .into_iter()
-        .map(|s| {
-            s.chars()
-                .map(|c| c.to_string().parse::<i32>().unwrap())
-                .collect()
-        })
-        .collect();
-    let rows = floor.len();
-    let columns = floor.get(0).unwrap().len();
-    let mut basins = basins(floor);
-    println!("{:?}", std::time::Instant::now() - start);
-    basins.sort_by(|a, b| a.len().cmp(&b.len()));
-    let end = std::time::Instant::now();
-    let duration = end - start;
-    println!("{:?} for ({}, {})", duration, rows, columns);
+pub fn parse(input: &str) -> Result<Floor> {
+    let rows = parse_lines(input, |line| {
+        line.char_indices()
+            .map(|(column, c)| {
+                c.to_digit(10)
+                    .map(|d| d as Height)
+                    .ok_or_else(|| ParseError::at(line, column, format!("bad height {c:?}")))
+            })
+            .collect()
+    })?;
+    Grid::from_rows(rows)
 }
 
-fn collect_basin(
-    floor: &Vec<Vec<i32>>,
-    point: (usize, usize),
-    mut accum: BTreeSet<(usize, usize)>,
-) -> BTreeSet<(usize, usize)> {
-    if accum.contains(&point) {
-        return accum;
-    } else {
-        accum.insert(point);
-    }
-    let (row_idx, col_idx) = point;
-    let rows = floor.len();
-    let columns = floor.get(0).unwrap().len();
-    for (nrow, ncol) in Neighbors::iter(row_idx as i32, rows as i32, col_idx as i32, columns as i32)
-    {
-        if floor[nrow as usize][ncol as usize] != 9 && !accum.contains(&(nrow as usize, ncol as usize)) {
-            let neighbor = (nrow as usize, ncol as usize);
-            accum = collect_basin(floor, neighbor, accum);
-        }
-    }
-    accum
+pub fn part_one(floor: &Floor) -> Result<u32> {
+    Ok(low_points(floor)
+        .map(|position| floor[position] as u32 + 1)
+        .sum())
 }
 
-fn basins(floor: Vec<Vec<i32>>) -> Vec<BTreeSet<(usize, usize)>> {
-    let mut basins = Vec::new();
-    let rows = floor.len();
-    let columns = floor.get(0).unwrap().len();
-    for row_idx in 0..rows {
-        for col_idx in 0..columns {
-            let value = floor[row_idx][col_idx];
-            if value == 9 {
-                continue;
-            }
-            if !basins
-                .iter()
-                .any(|b: &BTreeSet<(usize, usize)>| b.contains(&(row_idx, col_idx)))
-            {
-                basins.push(collect_basin(&floor, (row_idx, col_idx), BTreeSet::new()));
-            }
-        }
-    }
-    basins
+pub fn part_two(floor: &Floor) -> Result<usize> {
+    let basins = basins(floor);
+    let mut sizes: Vec<_> = basins.regions().iter().map(|basin| basin.size()).collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(sizes.into_iter().take(3).product())
 }
 
+aoc_core::example_tests!(crate::YEAR, 9);
+
 #[test]
 fn example() {
-    let input = r#"2199943210
-3987894921
-9856789892
-8767896789
-9899965678"#;
-    let lines: Vec<String> = input.split("\n").map(|s| s.to_string()).collect();
-    let floor: Vec<Vec<i32>> = lines
-        .into_iter()
-        .map(|s| {
-            s.chars()
-                .map(|c| c.to_string().parse::<i32>().unwrap())
-                .collect()
+    let floor = parse(&read_example(9, 1).unwrap()).unwrap();
+    let basins = basins(&floor);
+    let summary: Vec<_> = basins
+        .regions()
+        .iter()
+        .map(|basin| {
+            (
+                basin.size(),
+                basin.lowest(&floor),
+                basin.top_left,
+                basin.bottom_right,
+            )
         })
         .collect();
-    assert_eq!(risk_level(floor.clone()), 15);
-    let mut basins = basins(floor);
-    basins.sort_by(|a, b| a.len().cmp(&b.len()));
     assert_eq!(
-        basins
-            .into_iter()
-            .rev()
-            .take(3)
-            .inspect(|b| println!("{:?}", b))
-            .map(|b| b.len())
-            .product::<usize>(),
-        1134
+        summary,
+        vec![
+            (3, (0, 1), (0, 0), (1, 1)),
+            (9, (0, 9), (0, 5), (2, 9)),
+            (14, (2, 2), (1, 0), (4, 5)),
+            (9, (4, 6), (2, 5), (4, 9)),
+        ]
     );
+    // every basin drains to a single low point
+    let lowest: Vec<_> = basins
+        .regions()
+        .iter()
+        .map(|basin| basin.lowest(&floor))
+        .collect();
+    assert_eq!(low_points(&floor).collect::<Vec<_>>(), lowest);
+    assert!(render_basins(&floor).starts_with("\x1b[31m2\x1b[0m\x1b[31m1\x1b[0m9"));
+
+    // walling off everything from 8 up shrinks the larger basins
+    let lowlands = Regions::find(&floor, |&height| height >= 8);
+    let sizes: Vec<_> = lowlands
+        .regions()
+        .iter()
+        .map(|basin| basin.size())
+        .collect();
+    assert_eq!(sizes, vec![3, 9, 7, 6]);
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(9).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 417);
+    assert_eq!(part_two(&input).unwrap(), 1148965);
 }
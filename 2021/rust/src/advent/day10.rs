@@ -1,102 +1,261 @@
 use std::collections::HashMap;
 
 use crate::utils::*;
+use aoc_core::parse::ParseError;
+use eyre::{ContextCompat, Report};
 
-fn init_points() -> HashMap<char, usize> {
-    HashMap::from_iter([(')', 3), (']', 57), ('}', 1197), ('>', 25137)])
+/// What is wrong with a line of chunks, if anything.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Diagnosis {
+    /// Every chunk is closed.
+    Complete,
+    /// Some chunks are still open, and `completion` closes them, innermost first.
+    Incomplete { completion: String },
+    /// The character at `column` closes a chunk it does not match. `expected` is the closer that
+    /// would have been legal there, or `None` if no chunk was open.
+    Corrupted {
+        column: usize,
+        found: char,
+        expected: Option<char>,
+    },
 }
 
-fn init_pairs() -> HashMap<char, char> {
-    HashMap::from_iter([('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')])
+/// Which characters open chunks, and the closer for each.
+#[derive(Debug, Clone)]
+pub struct Brackets {
+    closers: HashMap<char, char>,
 }
 
-fn score_corrupted(lines: Vec<String>) -> usize {
-    let pairs = init_pairs();
-    let mut score: HashMap<char, usize> = HashMap::new();
-    for line in lines {
-        let mut iter = line.chars();
-        let mut state = vec![pairs.get(&iter.next().unwrap()).unwrap()];
-        for item in iter {
-            if pairs.contains_key(&item) {
-                state.push(pairs.get(&item).unwrap());
-            } else if item != *state[state.len() - 1] {
-                let entry = score.entry(item).or_insert(0);
-                *entry += 1;
-                break;
+impl Brackets {
+    pub fn new(pairs: impl IntoIterator<Item = (char, char)>) -> Self {
+        Brackets {
+            closers: pairs.into_iter().collect(),
+        }
+    }
+
+    fn is_closer(&self, c: char) -> bool {
+        self.closers.values().any(|&closer| closer == c)
+    }
+
+    /// Checks the chunks in a line, failing only on characters that are not brackets at all.
+    pub fn diagnose(&self, line: &str) -> Result<Diagnosis, ParseError> {
+        let mut open = Vec::new();
+        for (column, (offset, c)) in (1..).zip(line.char_indices()) {
+            if let Some(&closer) = self.closers.get(&c) {
+                open.push(closer);
+            } else if !self.is_closer(c) {
+                return Err(ParseError::at(
+                    line,
+                    offset,
+                    format!("{c:?} is not a bracket"),
+                ));
+            } else if open.last() == Some(&c) {
+                open.pop();
             } else {
-                state.pop();
+                return Ok(Diagnosis::Corrupted {
+                    column,
+                    found: c,
+                    expected: open.last().copied(),
+                });
             }
         }
+        Ok(if open.is_empty() {
+            Diagnosis::Complete
+        } else {
+            Diagnosis::Incomplete {
+                completion: open.iter().rev().collect(),
+            }
+        })
     }
-    let points = init_points();
-    score
-        .into_iter()
-        .map(|(i, c)| points.get(&i).unwrap() * c)
-        .sum()
-}
 
-pub fn part_one() {
-    let ip = problem_input_path(10, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    println!("{}", score_corrupted(lines));
-}
+    /// The line with its open chunks closed, or `None` if it is corrupted.
+    pub fn completed(&self, line: &str) -> Result<Option<String>, ParseError> {
+        Ok(match self.diagnose(line)? {
+            Diagnosis::Complete => Some(line.to_string()),
+            Diagnosis::Incomplete { completion } => Some(format!("{line}{completion}")),
+            Diagnosis::Corrupted { .. } => None,
+        })
+    }
 
-fn score_incomplete(lines: Vec<String>) -> usize {
-    let pairs = init_pairs();
-    let mut score: HashMap<char, usize> = HashMap::new();
-    let mut line_states = Vec::new();
-    for line in lines {
-        let mut iter = line.chars();
-        let mut state = vec![pairs.get(&iter.next().unwrap()).unwrap()];
-        let mut corrupted = false;
-        for item in iter {
-            if pairs.contains_key(&item) {
-                state.push(pairs.get(&item).unwrap());
-            } else if item != *state[state.len() - 1] {
-                let entry = score.entry(item).or_insert(0);
-                *entry += 1;
-                corrupted = true;
-                break;
-            } else {
-                state.pop();
+    /// The line made legal: each corrupted character is replaced by the closer expected there, or
+    /// dropped if no chunk was open, and then the open chunks are closed.
+    pub fn repaired(&self, line: &str) -> Result<String, ParseError> {
+        let mut chars: Vec<char> = line.chars().collect();
+        loop {
+            let line: String = chars.iter().collect();
+            match self.diagnose(&line)? {
+                Diagnosis::Corrupted {
+                    column,
+                    expected: Some(expected),
+                    ..
+                } => chars[column - 1] = expected,
+                Diagnosis::Corrupted { column, .. } => {
+                    chars.remove(column - 1);
+                }
+                Diagnosis::Complete => return Ok(line),
+                Diagnosis::Incomplete { completion } => return Ok(line + &completion),
             }
         }
-        if !corrupted {
-            line_states.push(state);
+    }
+}
+
+impl Default for Brackets {
+    /// The four kinds of chunk in the navigation subsystem.
+    fn default() -> Self {
+        Brackets::new([('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')])
+    }
+}
+
+/// The points for each illegal closer, and for each closer in a completion.
+#[derive(Debug, Clone)]
+pub struct Scoring {
+    illegal: HashMap<char, usize>,
+    completion: HashMap<char, usize>,
+}
+
+impl Scoring {
+    pub fn new(
+        illegal: impl IntoIterator<Item = (char, usize)>,
+        completion: impl IntoIterator<Item = (char, usize)>,
+    ) -> Self {
+        Scoring {
+            illegal: illegal.into_iter().collect(),
+            completion: completion.into_iter().collect(),
         }
     }
-    let points: HashMap<char, usize> = HashMap::from_iter([(')', 1), (']', 2), ('}', 3), ('>', 4)]);
-    let mut scores: Vec<usize> = line_states
+
+    pub fn illegal(&self, closer: char) -> Result<usize> {
+        self.illegal
+            .get(&closer)
+            .copied()
+            .wrap_err_with(|| format!("no score for illegal {closer:?}"))
+    }
+
+    /// Scores a completion by multiplying by five before adding each closer's points.
+    pub fn completion(&self, completion: &str) -> Result<usize> {
+        completion.chars().try_fold(0, |score, closer| {
+            let points = self
+                .completion
+                .get(&closer)
+                .wrap_err_with(|| format!("no score for completing with {closer:?}"))?;
+            Ok(score * 5 + points)
+        })
+    }
+}
+
+impl Default for Scoring {
+    /// The syntax checker's scores, and the autocomplete tool's.
+    fn default() -> Self {
+        Scoring::new(
+            [(')', 3), (']', 57), ('}', 1197), ('>', 25137)],
+            [(')', 1), (']', 2), ('}', 3), ('>', 4)],
+        )
+    }
+}
+
+/// Every line's diagnosis, in order.
+fn diagnose_all(lines: &[String], brackets: &Brackets) -> Result<Vec<Diagnosis>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            Ok(brackets
+                .diagnose(line)
+                .map_err(|error| error.on_line(index + 1))?)
+        })
+        .collect()
+}
+
+type Chunks = Vec<String>;
+
+pub fn parse(input: &str) -> Result<Chunks> {
+    split_on(input, "\n")
+}
+
+pub fn part_one(lines: &Chunks) -> Result<usize> {
+    let scoring = Scoring::default();
+    diagnose_all(lines, &Brackets::default())?
         .into_iter()
-        .map(|s| {
-            s.into_iter()
-                .rev()
-                .fold(0, |accum, item| accum * 5 + points.get(item).unwrap())
+        .map(|diagnosis| match diagnosis {
+            Diagnosis::Corrupted { found, .. } => scoring.illegal(found),
+            _ => Ok(0),
         })
-        .collect();
-    scores.sort_unstable();
-    scores[scores.len() / 2]
+        .sum()
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(10, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    println!("{}", score_incomplete(lines));
+pub fn part_two(lines: &Chunks) -> Result<usize> {
+    let scoring = Scoring::default();
+    let mut scores = diagnose_all(lines, &Brackets::default())?
+        .into_iter()
+        .filter_map(|diagnosis| match diagnosis {
+            Diagnosis::Incomplete { completion } => Some(scoring.completion(&completion)),
+            _ => None,
+        })
+        .collect::<Result<Vec<_>>>()?;
+    scores.sort_unstable();
+    scores
+        .get(scores.len() / 2)
+        .copied()
+        .ok_or_else(|| Report::msg("no incomplete lines"))
 }
 
+aoc_core::example_tests!(crate::YEAR, 10);
+
 #[test]
 fn example() {
-    let example = r#"[({(<(())[]>[[{[]{<()<>>
-[(()[<>])]({[<{<<[]>>(
-{([(<{}[<>[]}>{[]{[(<()>
-(((({<>}<{<{<>}{[]{[]{}
-[[<[([]))<([[{}[[()]]]
-[{[{({}]{}}([{[{{{}}([]
-{<[[]]>}<{[{[{[]{()[[[]
-[<(<(<(<{}))><([]([]()
-<{([([[(<>()){}]>(<<{{
-<{([{{}}[<[[[<>{}]]]>[]]"#;
-    let lines: Vec<String> = example.split("\n").map(|s| s.into()).collect();
-    assert_eq!(score_corrupted(lines.clone()), 26397);
-    assert_eq!(score_incomplete(lines), 288957);
+    let brackets = Brackets::default();
+    assert_eq!(
+        brackets.diagnose("{([(<{}[<>[]}>{[]{[(<()>").unwrap(),
+        Diagnosis::Corrupted {
+            column: 13,
+            found: '}',
+            expected: Some(']'),
+        }
+    );
+    let line = "[({(<(())[]>[[{[]{<()<>>";
+    assert_eq!(
+        brackets.diagnose(line).unwrap(),
+        Diagnosis::Incomplete {
+            completion: "}}]])})]".to_string(),
+        }
+    );
+    assert_eq!(Scoring::default().completion("}}]])})]").unwrap(), 288957);
+    assert_eq!(
+        brackets.completed(line).unwrap().unwrap(),
+        "[({(<(())[]>[[{[]{<()<>>}}]])})]"
+    );
+
+    for line in parse(&read_example(10, 1).unwrap()).unwrap() {
+        let repaired = brackets.repaired(&line).unwrap();
+        assert_eq!(brackets.diagnose(&repaired).unwrap(), Diagnosis::Complete);
+        // only corrupted lines have anything but their completion changed
+        assert!(repaired.starts_with(&line) || brackets.completed(&line).unwrap().is_none());
+    }
+    assert_eq!(brackets.repaired("(]>").unwrap(), "()");
+}
+
+#[test]
+fn custom_brackets() {
+    let brackets = Brackets::new([('«', '»'), ('⟨', '⟩')]);
+    assert_eq!(
+        brackets.diagnose("«⟨»").unwrap(),
+        Diagnosis::Corrupted {
+            column: 3,
+            found: '»',
+            expected: Some('⟩'),
+        }
+    );
+    assert_eq!(brackets.repaired("«⟨»").unwrap(), "«⟨⟩»");
+    let error = brackets.diagnose("«(»").unwrap_err();
+    assert_eq!(
+        (error.column, error.message.as_str()),
+        (2, "'(' is not a bracket")
+    );
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(10).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 266301);
+    assert_eq!(part_two(&input).unwrap(), 3404870164);
 }
@@ -1,144 +1,114 @@
-use itertools::{Itertools};
-use std::{fmt::Display, fs};
-
 use crate::utils::*;
+use aoc_core::automaton::{Automaton, Rule};
+use aoc_core::grid::{Grid, Position};
+use aoc_core::parse::{parse_lines, ParseError};
+use eyre::Report;
 
-type Octopus = usize;
+type Octopus = u8;
 
-// it's a pod of octopii!
-struct OctoPod<const N: usize> {
-    octopuses: [[Octopus; N]; N],
-    flash_count: usize,
-}
+/// Octopuses gain energy each step, and flash once they have more than 9, giving their
+/// neighbors energy too. Those that flashed start the next step from 0.
+#[derive(Debug, Clone)]
+pub struct Flashes;
 
-impl<const N: usize> Display for OctoPod<N> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.octopuses
-                .iter()
-                .map(|row| row.iter().join(""))
-                .join("\n")
-        )
-    }
-}
+impl Rule for Flashes {
+    type Cell = Octopus;
 
-impl<const N: usize> TryFrom<String> for OctoPod<N> {
-    type Error = ();
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let mut octopuses = [[Default::default(); N]; N];
-        for (row, line) in value.split_whitespace().enumerate() {
-            if row >= N {
-                return Err(());
-            }
-            for (column, char) in line.chars().enumerate() {
-                if column >= N {
-                    return Err(());
-                }
-                if let Some(energy_level) = char.to_digit(10) {
-                    octopuses[row][column] = energy_level as usize;
-                } else {
-                    return Err(());
-                }
-            }
-        }
-        Ok(Self::new(octopuses))
+    fn next(&self, grid: &Grid<Octopus>, position: Position) -> Octopus {
+        grid[position] + 1
     }
-}
 
-impl<const N: usize> OctoPod<N> {
-    fn new(octopuses: [[Octopus; N]; N]) -> Self {
-        Self {
-            octopuses,
-            flash_count: 0,
-        }
+    fn fires(&self, octopus: &Octopus) -> bool {
+        *octopus > 9
     }
-    fn neighbors(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
-        (-1 as i32..=1 as i32)
-            .cartesian_product(-1 as i32..=1 as i32)
-            .map(move |(xdelta, ydelta)| (row as i32 + xdelta, col as i32 + ydelta))
-            .filter(move |(r, c)| {
-                (0..N as i32).contains(r)
-                    && (0..N as i32).contains(c)
-                    && (row as i32, col as i32) != (*r, *c)
-            })
-            .map(|(r, c)| (r as usize, c as usize))
+
+    fn excite(&self, octopus: &mut Octopus) {
+        *octopus += 1;
     }
-    fn step(&mut self) -> bool {
-        self.octopuses
-            .iter_mut()
-            .for_each(|row| row.iter_mut().for_each(|o| *o += 1));
-        let mut has_flashed = [[false; N]; N];
-        let mut flash_occurred = true;
-        while flash_occurred {
-            flash_occurred = false;
-            for row in 0..N {
-                for col in 0..N {
-                    if self.octopuses[row][col] > 9 && !has_flashed[row][col] {
-                        flash_occurred = true;
-                        has_flashed[row][col] = true;
-                        for (nrow, ncol) in self.neighbors(row, col) {
-                            self.octopuses[nrow][ncol] += 1;
-                        }
-                    }
-                }
-            }
-        }
-        for row in 0..N {
-            for col in 0..N {
-                if has_flashed[row][col] {
-                    self.flash_count += 1;
-                    self.octopuses[row][col] = 0;
-                }
-            }
+
+    fn settle(&self, octopus: &mut Octopus, flashed: bool) {
+        if flashed {
+            *octopus = 0;
         }
-        has_flashed.iter().flatten().all(|b| *b)
-    }
-    fn flashes(&self) -> usize {
-        self.flash_count
     }
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(11, Some(1));
-    let mut pod: OctoPod<10> = fs::read_to_string(&ip).unwrap().try_into().unwrap();
-    for _ in 0..100 {
-        pod.step();
-    }
-    println!("{}", pod.flashes());
+// it's a pod of octopii!
+type OctoPod = Automaton<Flashes>;
+
+/// Draws an octopus in a frame by its energy level, as the statement does.
+pub fn render(octopus: &Octopus) -> char {
+    char::from_digit(*octopus as u32, 10).unwrap_or('*')
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(11, Some(1));
-    let mut pod: OctoPod<10> = fs::read_to_string(&ip).unwrap().try_into().unwrap();
-    let mut step = 1;
-    while !pod.step() {
-        step += 1;
-    }
-    println!("{}", step);
+pub fn parse(input: &str) -> Result<OctoPod> {
+    let rows = parse_lines(input, |line| {
+        let line = line.trim();
+        line.char_indices()
+            .map(|(column, c)| {
+                c.to_digit(10)
+                    .map(|energy| energy as Octopus)
+                    .ok_or_else(|| ParseError::at(line, column, format!("bad energy level {c:?}")))
+            })
+            .collect()
+    })?;
+    Ok(Automaton::new(Flashes, Grid::from_rows(rows)?))
+}
+
+pub fn part_one(pod: &OctoPod) -> Result<usize> {
+    let mut pod = pod.clone();
+    let mut flashes = 0;
+    pod.run(100, |_, step| flashes += step.fired);
+    Ok(flashes)
+}
+
+pub fn part_two(pod: &OctoPod) -> Result<usize> {
+    pod.clone()
+        .run_until_synchronized(10_000)
+        .ok_or_else(|| Report::msg("the octopuses never all flash together"))
 }
 
+aoc_core::example_tests!(crate::YEAR, 11);
+
 #[test]
 fn example() {
-    let example = r#"5483143223
-2745854711
-5264556173
-6141336146
-6357385478
-4167524645
-2176841721
-6882881134
-4846848554
-5283751526"#;
-    let mut pod: OctoPod<10> = example.to_string().try_into().unwrap();
-    for _ in 0..10 {
-        pod.step();
-    }
-    assert_eq!(pod.flashes(), 204);
-    for _ in 0..90 {
-        pod.step();
-    }
-    assert_eq!(pod.flashes(), 1656);
+    let mut pod = parse(&read_example(11, 1).unwrap()).unwrap();
+    let mut flashes = 0;
+    let mut frames = Vec::new();
+    pod.run(10, |pod, step| {
+        flashes += step.fired;
+        frames.push(pod.frame(render));
+    });
+    assert_eq!(flashes, 204);
+    assert_eq!(
+        frames[0],
+        "6594254334\n3856965822\n6375667284\n7252447257\n7468496589\n\
+         5278635756\n3287952832\n7993992245\n5957959665\n6394862637\n"
+    );
+    assert_eq!(
+        frames[1],
+        "8807476555\n5089087054\n8597889608\n8485769600\n8700908800\n\
+         6600088989\n6800005943\n0000007456\n9000000876\n8700006848\n"
+    );
+
+    let mut pod = parse("11111\n19991\n19191\n19991\n11111\n").unwrap();
+    let mut frames = Vec::new();
+    pod.run(2, |pod, _| frames.push(pod.frame(render)));
+    assert_eq!(frames[0], "34543\n40004\n50005\n40004\n34543\n");
+    assert_eq!(frames[1], "45654\n51115\n61116\n51115\n45654\n");
+    // nor do pods have to be square
+    let mut pod = parse("1111\n1991\n1111\n").unwrap();
+    assert_eq!(pod.step().fired, 2);
+    assert_eq!(pod.frame(render), "3443\n3003\n3443\n");
+
+    let error = parse("  11\n  1x\n").unwrap_err();
+    let error = error.downcast::<ParseError>().unwrap();
+    assert_eq!((error.line, error.column), (2, 2));
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(11).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 1601);
+    assert_eq!(part_two(&input).unwrap(), 368);
 }
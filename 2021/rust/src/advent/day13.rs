@@ -1,121 +1,257 @@
-use std::collections::HashSet;
+use std::fmt::{self, Display};
 
-use regex::Regex;
+use aoc_core::grid::Grid;
+use aoc_core::parse::ParseError;
+use aoc_core::scan;
+use aoc_core::sparse_grid::{Coordinate, SparseGrid};
 
 use crate::utils::*;
+use eyre::ContextCompat;
 
-fn parse_input(lines: Vec<String>) -> (HashSet<(usize, usize)>, Vec<Fold>) {
-    let mut dot_coords = HashSet::new();
-    for dot_line in lines.iter().take_while(|s| !s.is_empty()) {
-        let mut coords = dot_line.split(",");
-        let x: usize = coords.next().unwrap().parse().unwrap();
-        let y: usize = coords.next().unwrap().parse().unwrap();
-        dot_coords.insert((x, y));
-    }
+type Dots = SparseGrid<()>;
+type Manual = (Paper, Vec<Fold>);
+
+pub fn parse(input: &str) -> Result<Manual> {
+    let mut dots = SparseGrid::new();
     let mut folds = Vec::new();
-    let fold_re = Regex::new(r"([xy])=(\d+)").unwrap();
-    for fold_line in lines.iter().skip(dot_coords.len() + 1) {
-        let fold_captures = fold_re.captures(fold_line).unwrap();
-        let axis = &fold_captures[1];
-        let coord = &fold_captures[2];
-        match axis {
-            "x" => folds.push(Fold::Vertical(coord.parse().unwrap())),
-            "y" => folds.push(Fold::Horiztonal(coord.parse().unwrap())),
-            _ => panic!("invalid axis"),
+    let mut in_folds = false;
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            in_folds = true;
+            continue;
         }
+        let parsed = if in_folds {
+            parse_fold(line).map(|fold| folds.push(fold))
+        } else {
+            parse_dot(line).map(|dot| {
+                dots.insert(dot, ());
+            })
+        };
+        parsed.map_err(|error| error.on_line(index + 1))?;
     }
-    (dot_coords, folds)
+    Ok((Paper::new(dots), folds))
 }
 
-#[derive(Debug)]
-enum Fold {
-    Vertical(usize),
-    Horiztonal(usize),
+fn parse_dot(line: &str) -> std::result::Result<Coordinate, ParseError> {
+    let (x, y) = scan!(line, "{x},{y}" => usize, usize)?;
+    Ok((x as i64, y as i64))
 }
 
-fn perform_fold(coords: HashSet<(usize, usize)>, fold: Fold) -> HashSet<(usize, usize)> {
-    let mut result = HashSet::new();
-    for (x, y) in coords {
-        match fold {
-            Fold::Vertical(f) => {
-                if x != f {
-                    let new_x = f - (f as i32 - x as i32).abs() as usize;
-                    result.insert((new_x, y));
-                }
-            }
-            Fold::Horiztonal(f) => {
-                if y != f {
-                    let new_y = f - (f as i32 - y as i32).abs() as usize;
-                    result.insert((x, new_y));
-                }
-            }
+fn parse_fold(line: &str) -> std::result::Result<Fold, ParseError> {
+    match scan!(line, "fold along {axis}={at}" => char, usize)? {
+        ('x', at) => Ok(Fold::Vertical(at as i64)),
+        ('y', at) => Ok(Fold::Horizontal(at as i64)),
+        _ => Err(ParseError::at(line, 11, "the axis should be x or y")),
+    }
+}
+
+/// A fold along a vertical line `x=` some column, or a horizontal line `y=` some row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fold {
+    Vertical(i64),
+    Horizontal(i64),
+}
+
+impl Display for Fold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fold::Vertical(at) => write!(f, "fold along x={at}"),
+            Fold::Horizontal(at) => write!(f, "fold along y={at}"),
         }
     }
-    result
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(13, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let (mut coords, folds) = parse_input(lines);
-    let mut folds = folds.into_iter();
-    coords = perform_fold(coords, folds.next().unwrap());
-    println!("{}", coords.len());
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FoldError {
+    /// The fold line runs through a dot, which would end up on the crease rather than on
+    /// either half.
+    DotOnLine { fold: Fold, dot: Coordinate },
+    /// The fold line is not on the paper.
+    OffThePaper { fold: Fold, width: i64, height: i64 },
 }
 
-fn print_coords(coords: HashSet<(usize, usize)>) {
-    let mut text = String::new();
-    let x_max = *coords.iter().map(|(x, _)| x).max().unwrap();
-    let y_max = *coords.iter().map(|(_, y)| y).max().unwrap();
-    for y in 0..=y_max {
-        for x in 0..=x_max {
-            if coords.contains(&(x, y)) {
-                text.extend(std::iter::once('#'));
-            } else {
-                text.extend(std::iter::once('.'));
+impl Display for FoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FoldError::DotOnLine { fold, dot: (x, y) } => {
+                write!(f, "cannot {fold}, as there is a dot on the line at {x},{y}")
             }
+            FoldError::OffThePaper {
+                fold,
+                width,
+                height,
+            } => write!(f, "cannot {fold} on paper {width} wide and {height} high"),
         }
-        text.extend(std::iter::once('\n'));
     }
-    println!("{}", text);
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(13, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let (mut coords, folds) = parse_input(lines);
-    for fold in folds {
-        coords = perform_fold(coords, fold);
+impl std::error::Error for FoldError {}
+
+/// A sheet of transparent paper with dots on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paper {
+    dots: Dots,
+    width: i64,
+    height: i64,
+}
+
+impl Paper {
+    /// Paper just large enough to hold every dot.
+    pub fn new(dots: Dots) -> Self {
+        let (width, height) = dots
+            .bounds()
+            .map_or((0, 0), |bounds| (bounds.max.0 + 1, bounds.max.1 + 1));
+        Paper {
+            dots,
+            width,
+            height,
+        }
+    }
+
+    pub fn dots(&self) -> &Dots {
+        &self.dots
+    }
+
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    pub fn height(&self) -> i64 {
+        self.height
     }
-    print_coords(coords);
+
+    /// The paper folded along `fold`, with the right or bottom half folded over onto the other.
+    ///
+    /// The halves need not be the same size. The folded paper is as large as the larger half,
+    /// with the fold line as its right or bottom edge, so when the far half is the larger it
+    /// sticks out past the near half's left or top edge, and everything is shifted to keep the
+    /// top left at `(0, 0)`.
+    pub fn fold(&self, fold: Fold) -> std::result::Result<Paper, FoldError> {
+        let (at, length) = match fold {
+            Fold::Vertical(at) => (at, self.width),
+            Fold::Horizontal(at) => (at, self.height),
+        };
+        if !(0..length).contains(&at) {
+            return Err(FoldError::OffThePaper {
+                fold,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        let folded = at.max(length - 1 - at);
+        // both halves count their distance from the fold line back from the new edge
+        let reflect = |along: i64| folded - (along - at).abs();
+        let mut dots = SparseGrid::new();
+        for (x, y) in self.dots.coordinates() {
+            let along = match fold {
+                Fold::Vertical(_) => x,
+                Fold::Horizontal(_) => y,
+            };
+            if along == at {
+                return Err(FoldError::DotOnLine { fold, dot: (x, y) });
+            }
+            let dot = match fold {
+                Fold::Vertical(_) => (reflect(x), y),
+                Fold::Horizontal(_) => (x, reflect(y)),
+            };
+            dots.insert(dot, ());
+        }
+        let (width, height) = match fold {
+            Fold::Vertical(_) => (folded, self.height),
+            Fold::Horizontal(_) => (self.width, folded),
+        };
+        Ok(Paper {
+            dots,
+            width,
+            height,
+        })
+    }
+
+    /// The paper after every fold in turn, with how many dots were visible after each.
+    pub fn fold_all(&self, folds: &[Fold]) -> std::result::Result<(Paper, Vec<usize>), FoldError> {
+        let mut paper = self.clone();
+        let mut counts = Vec::with_capacity(folds.len());
+        for &fold in folds {
+            paper = paper.fold(fold)?;
+            counts.push(paper.dots.len());
+        }
+        Ok((paper, counts))
+    }
+
+    /// Where the dots are, from the top left of the paper to the furthest dot, leaving off any
+    /// blank rows and columns past that.
+    pub fn image(&self) -> Grid<bool> {
+        let (columns, rows) = self
+            .dots
+            .bounds()
+            .map_or((0, 0), |bounds| (bounds.max.0 + 1, bounds.max.1 + 1));
+        let mut image = Grid::filled(rows as usize, columns as usize, false);
+        for (x, y) in self.dots.coordinates() {
+            image[(y as usize, x as usize)] = true;
+        }
+        image
+    }
+}
+
+fn render(image: &Grid<bool>) -> String {
+    image
+        .display(|&dot| if dot { '#' } else { '.' })
+        .to_string()
+}
+
+pub fn part_one((paper, folds): &Manual) -> Result<usize> {
+    let &fold = folds.first().wrap_err("no folds in the manual")?;
+    Ok(paper.fold(fold)?.dots().len())
+}
+
+pub fn part_two((paper, folds): &Manual) -> Result<String> {
+    let (paper, _) = paper.fold_all(folds)?;
+    Ok(render(&paper.image()))
 }
 
+aoc_core::example_tests!(crate::YEAR, 13);
+
 #[test]
 fn example() {
-    let example = r#"6,10
-0,14
-9,10
-0,3
-10,4
-4,11
-6,0
-6,12
-4,1
-0,13
-10,12
-3,4
-3,0
-8,4
-1,10
-2,14
-8,10
-9,0
-
-fold along y=7
-fold along x=5"#;
-    let lines = example.split("\n").map(|s| s.to_string()).collect();
-    let (mut coords, folds) = parse_input(lines);
-    let mut folds = folds.into_iter();
-    coords = perform_fold(coords, folds.next().unwrap());
-    assert_eq!(coords.len(), 17);
+    let (paper, folds) = parse(&read_example(13, 1).unwrap()).unwrap();
+    assert_eq!((paper.width(), paper.height()), (11, 15));
+    let (folded, counts) = paper.fold_all(&folds).unwrap();
+    assert_eq!(counts, vec![17, 16]);
+    assert_eq!((folded.width(), folded.height()), (5, 7));
+    let image = folded.image();
+    assert_eq!((image.rows(), image.columns()), (5, 5));
+
+    // folding short of the middle leaves the far half sticking out past the near one
+    let (paper, folds) = parse("0,0\n2,0\n4,1\n\nfold along x=1\nfold along y=0\n").unwrap();
+    let folded = paper.fold(folds[0]).unwrap();
+    assert_eq!((folded.width(), folded.height()), (3, 2));
+    assert_eq!(render(&folded.image()), "..#\n#..\n");
+    assert_eq!(
+        paper.fold_all(&folds),
+        Err(FoldError::DotOnLine {
+            fold: Fold::Horizontal(0),
+            dot: (2, 0),
+        })
+    );
+    assert_eq!(
+        paper.fold(Fold::Vertical(5)).unwrap_err().to_string(),
+        "cannot fold along x=5 on paper 5 wide and 2 high"
+    );
+    let error = parse("0,0\n1;2\n\nfold along x=1\n").unwrap_err();
+    assert_eq!(error.downcast::<ParseError>().unwrap().line, 2);
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(13).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 716);
+    let image = r#"###..###...##..#..#.####.###..#....###.
+#..#.#..#.#..#.#.#..#....#..#.#....#..#
+#..#.#..#.#....##...###..###..#....#..#
+###..###..#....#.#..#....#..#.#....###.
+#.#..#....#..#.#.#..#....#..#.#....#.#.
+#..#.#.....##..#..#.#....###..####.#..#
+"#;
+    assert_eq!(part_two(&input).unwrap(), image);
 }
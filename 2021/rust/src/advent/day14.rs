@@ -1,17 +1,35 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use eyre::{ContextCompat, Report};
+use itertools::{Itertools, MinMaxResult};
 
 use crate::utils::*;
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::scan;
 
-fn parse_input(
-    lines: Vec<String>,
-) -> (
+type Polymer = (
     char,
     HashMap<(char, char), usize>,
     HashMap<(char, char), char>,
-) {
-    let template: Vec<char> = lines[0].chars().collect();
+);
+
+fn parse_rule(line: &str) -> std::result::Result<((char, char), char), ParseError> {
+    let (pair, inserted) = scan!(line, "{pair} -> {inserted}" => String, char)?;
+    let pair = pair
+        .chars()
+        .collect_tuple()
+        .ok_or_else(|| ParseError::at(line, 0, "a rule should match a pair of elements"))?;
+    Ok((pair, inserted))
+}
+
+pub fn parse(input: &str) -> Result<Polymer> {
+    let lines: Vec<&str> = input.trim().lines().collect();
+    let template: Vec<char> = lines
+        .first()
+        .wrap_err("missing template")?
+        .chars()
+        .collect();
+    let &extra = template.first().wrap_err("empty template")?;
     let mut counts = HashMap::new();
     for window in template.windows(2) {
         if let [first, second] = window {
@@ -19,28 +37,27 @@ fn parse_input(
         }
     }
     let mut pairs = HashMap::new();
-    for line in lines.into_iter().skip(2) {
-        let mut split = line.split(" -> ");
-        let mut input = split.next().unwrap().chars();
-        pairs.insert(
-            (input.next().unwrap(), input.next().unwrap()),
-            split.next().unwrap().chars().next().unwrap(),
-        );
+    // the rules start after the template and a blank line
+    let rules = lines.get(2..).unwrap_or_default().join("\n");
+    for (pair, inserted) in parse_lines(&rules, parse_rule).map_err(|error| error.on_line(3))? {
+        pairs.insert(pair, inserted);
     }
-    (template[0], counts, pairs)
+    Ok((extra, counts, pairs))
 }
 
 fn process(
-    counts: HashMap<(char, char), usize>,
+    counts: &HashMap<(char, char), usize>,
     pairs: &HashMap<(char, char), char>,
-) -> HashMap<(char, char), usize> {
+) -> Result<HashMap<(char, char), usize>> {
     let mut new_counts = HashMap::new();
-    for ((first, last), count) in counts {
-        let middle = *pairs.get(&(first, last)).unwrap();
+    for (&(first, last), &count) in counts {
+        let middle = *pairs
+            .get(&(first, last))
+            .wrap_err_with(|| format!("no rule for {}{}", first, last))?;
         *new_counts.entry((first, middle)).or_insert(0) += count;
         *new_counts.entry((middle, last)).or_insert(0) += count;
     }
-    new_counts
+    Ok(new_counts)
 }
 
 fn count_letters(counts: HashMap<(char, char), usize>, extra: char) -> HashMap<char, usize> {
@@ -54,71 +71,47 @@ fn count_letters(counts: HashMap<(char, char), usize>, extra: char) -> HashMap<c
     letter_counts
 }
 
+fn most_minus_least(polymer: &Polymer, steps: usize) -> Result<usize> {
+    let (extra, counts, pairs) = polymer;
+    let mut counts = counts.clone();
+    for _ in 0..steps {
+        counts = process(&counts, pairs)?;
+    }
+    let letter_counts = count_letters(counts, *extra);
+    match letter_counts.values().minmax() {
+        MinMaxResult::NoElements => Err(Report::msg("polymer has no letters")),
+        MinMaxResult::OneElement(_) => Ok(0),
+        MinMaxResult::MinMax(least, most) => Ok(most - least),
+    }
+}
+
 // TODO: improvements
 // - [char; 2] is hashable
 // - counts.values().max().unwrap() - counts.values().min().unwrap() is O(2N) instead of O(N log N) where log N is almost certainly higher than 2
-// - only difference is step count
-pub fn part_one() {
-    let ip = problem_input_path(14, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let (extra, mut counts, pairs) = parse_input(lines);
-    for _ in 0..10 {
-        counts = process(counts, &pairs);
-    }
-    let letter_counts: Vec<usize> = count_letters(counts, extra)
-        .values()
-        .copied()
-        .sorted()
-        .collect();
-    let result = letter_counts[letter_counts.len() - 1] - letter_counts[0];
-    println!("{}", result);
+pub fn part_one(polymer: &Polymer) -> Result<usize> {
+    most_minus_least(polymer, 10)
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(14, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let (extra, mut counts, pairs) = parse_input(lines);
-    for _ in 0..40 {
-        counts = process(counts, &pairs);
-    }
-    let letter_counts: Vec<usize> = count_letters(counts, extra)
-        .values()
-        .copied()
-        .sorted()
-        .collect();
-    let result = letter_counts[letter_counts.len() - 1] - letter_counts[0];
-    println!("{}", result);
+pub fn part_two(polymer: &Polymer) -> Result<usize> {
+    most_minus_least(polymer, 40)
 }
 
+aoc_core::example_tests!(crate::YEAR, 14);
+
 #[test]
 fn example() {
-    let example = r#"NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C"#;
-    let lines = example.split("\n").map(|s| s.to_string()).collect();
-    let (extra, mut counts, pairs) = parse_input(lines);
+    let (extra, mut counts, pairs) = parse(&read_example(14, 1).unwrap()).unwrap();
     for _ in 0..10 {
-        counts = process(counts, &pairs);
+        counts = process(&counts, &pairs).unwrap();
     }
     let letter_counts = count_letters(counts, extra);
     let expected_counts = HashMap::from_iter([('B', 1749), ('C', 298), ('H', 161), ('N', 865)]);
     assert_eq!(letter_counts, expected_counts);
-    let letter_counts: Vec<usize> = letter_counts.values().copied().sorted().collect();
-    let result = letter_counts[letter_counts.len() - 1] - letter_counts[0];
-    assert_eq!(result, 1588);
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(14).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 3247);
+    assert_eq!(part_two(&input).unwrap(), 4110568157153);
 }
@@ -1,189 +1,285 @@
-use std::collections::{BinaryHeap, HashSet};
-
 use crate::utils::*;
+use aoc_core::grid::{Grid, Position};
+use aoc_core::parse::{parse_lines, ParseError};
+use eyre::Report;
+
+type Risk = u32;
+type RiskMap = Grid<Risk>;
+
+/// The highest risk of any position, so no step costs more.
+const MAX_RISK: Risk = 9;
 
-const CARDINAL_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+/// Enough buckets of positions waiting to be visited that the nearest and furthest never share.
+const BUCKETS: usize = MAX_RISK as usize + 1;
 
-#[derive(Debug, Clone)]
-struct Path {
-    path: Vec<(i32, i32)>,
-    risk: u32,
+pub fn parse(input: &str) -> Result<RiskMap> {
+    let rows = parse_lines(input, |line| {
+        line.char_indices()
+            .map(|(column, c)| {
+                c.to_digit(10)
+                    .filter(|&risk| risk > 0)
+                    .ok_or_else(|| ParseError::at(line, column, format!("bad risk {c:?}")))
+            })
+            .collect()
+    })?;
+    Grid::from_rows(rows)
 }
 
-impl PartialEq for Path {
-    fn eq(&self, other: &Self) -> bool {
-        self.risk == other.risk
-    }
+/// The whole cave: the map repeated `tiles` times across and down, with every tile's risks one
+/// higher than the tile above or to its left, wrapping from 9 back to 1.
+#[derive(Debug, Clone, Copy)]
+pub struct Cave<'a> {
+    map: &'a RiskMap,
+    tiles: usize,
 }
 
-impl Eq for Path {}
+impl<'a> Cave<'a> {
+    pub fn new(map: &'a RiskMap, tiles: usize) -> Result<Self> {
+        if tiles == 0 {
+            return Err(Report::msg("the cave needs at least one tile"));
+        }
+        if map.rows() == 0 || map.columns() == 0 {
+            return Err(Report::msg("the risk map is empty"));
+        }
+        Ok(Cave { map, tiles })
+    }
 
-impl PartialOrd for Path {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.risk.partial_cmp(&other.risk).map(|o| o.reverse())
+    pub fn rows(&self) -> usize {
+        self.map.rows() * self.tiles
     }
-}
 
-impl Ord for Path {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.risk.cmp(&other.risk).reverse()
+    pub fn columns(&self) -> usize {
+        self.map.columns() * self.tiles
     }
-}
 
-fn parse_input(lines: Vec<String>) -> Vec<Vec<u32>> {
-    let mut map = Vec::new();
-    for line in lines {
-        let mut row = Vec::new();
-        for c in line.chars() {
-            row.push(c.to_digit(10).unwrap());
-        }
-        map.push(row);
+    /// The risk of entering `(row, column)`, worked out from the map rather than stored.
+    pub fn risk(&self, (row, column): Position) -> Risk {
+        let (rows, columns) = (self.map.rows(), self.map.columns());
+        let tile = (row / rows + column / columns) as Risk;
+        (self.map[(row % rows, column % columns)] + tile - 1) % MAX_RISK + 1
     }
-    map
-}
 
-fn find_best_path(map: &Vec<Vec<u32>>) -> Path {
-    let start = (0 as i32, 0 as i32);
-    let (end_r, end_c) = ((map.len() - 1) as i32, (map[0].len() - 1) as i32);
-    let mut visited = HashSet::new();
-    let mut frontier = BinaryHeap::new();
-    // The starting position is not 'entered', so its risk does not count
-    frontier.push(Path {
-        path: vec![start],
-        risk: 0,
-    });
-    visited.insert(start);
-    while let Some(path) = frontier.pop() {
-        // println!("visiting {:?}", path);
-        let tip = path.path[path.path.len() - 1];
-        let (r, c) = tip;
-        for (dr, dc) in CARDINAL_DIRECTIONS {
-            let (nr, nc) = (r + dr, c + dc);
-            if !visited.contains(&(nr, nc)) && 0 <= nr && nr <= end_r && 0 <= nc && nc <= end_c {
-                let mut neighbor_path = path.path.clone();
-                neighbor_path.push((nr, nc));
-                let neighbor_risk = map[nr as usize][nc as usize];
-                let neighbor = Path {
-                    path: neighbor_path,
-                    risk: path.risk + neighbor_risk,
-                };
-                if nr == end_r && nc == end_c {
-                    return neighbor;
+    fn index(&self, (row, column): Position) -> usize {
+        row * self.columns() + column
+    }
+
+    fn position(&self, index: usize) -> Position {
+        (index / self.columns(), index % self.columns())
+    }
+
+    fn neighbors(&self, (row, column): Position) -> impl Iterator<Item = Position> {
+        let (rows, columns) = (self.rows(), self.columns());
+        [
+            (row.wrapping_sub(1), column),
+            (row, column + 1),
+            (row + 1, column),
+            (row, column.wrapping_sub(1)),
+        ]
+        .into_iter()
+        .filter(move |&(r, c)| r < rows && c < columns)
+    }
+
+    /// The least risky path from the top left to the bottom right.
+    ///
+    /// No step costs more than [`MAX_RISK`], so the positions waiting to be visited only ever
+    /// span that many distances past the nearest one. They are kept in a ring of buckets, one
+    /// per distance, rather than a heap, and distances in an array indexed like the cave.
+    pub fn safest_path(&self) -> Result<Path> {
+        let start = self.index((0, 0));
+        let end = self.index((self.rows() - 1, self.columns() - 1));
+        let mut risk = vec![Risk::MAX; self.rows() * self.columns()];
+        let mut previous = vec![usize::MAX; risk.len()];
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); BUCKETS];
+        let mut waiting = 1;
+        // the starting position is not 'entered', so its risk does not count
+        risk[start] = 0;
+        buckets[0].push(start);
+
+        let mut current: Risk = 0;
+        while waiting > 0 {
+            let bucket = current as usize % BUCKETS;
+            while let Some(index) = buckets[bucket].pop() {
+                waiting -= 1;
+                if risk[index] != current {
+                    // reached more cheaply since it was queued
+                    continue;
+                }
+                if index == end {
+                    return Ok(self.path(&previous, end, current));
+                }
+                for neighbor in self.neighbors(self.position(index)) {
+                    let via = current + self.risk(neighbor);
+                    let neighbor = self.index(neighbor);
+                    if via < risk[neighbor] {
+                        risk[neighbor] = via;
+                        previous[neighbor] = index;
+                        buckets[via as usize % BUCKETS].push(neighbor);
+                        waiting += 1;
+                    }
                 }
-                frontier.push(neighbor);
-                visited.insert((nr, nc));
             }
+            current += 1;
         }
+        Err(Report::msg("no path reaches the bottom right corner"))
     }
-    panic!("we should have returned the end path by now")
-}
 
-fn find_best_path_tiled(map: &Vec<Vec<u32>>) -> Path {
-    let start = (0 as i32, 0 as i32);
-    let (rows, columns) = (map.len() as i32, map[0].len() as i32);
-    let (end_r, end_c) = (rows * 5 - 1 as i32, columns * 5 - 1 as i32);
-    let mut visited = HashSet::new();
-    let mut frontier = BinaryHeap::new();
-    // The starting position is not 'entered', so its risk does not count
-    frontier.push(Path {
-        path: vec![start],
-        risk: 0,
-    });
-    visited.insert(start);
-    while let Some(path) = frontier.pop() {
-        // println!("visiting {:?}", path);
-        let tip = path.path[path.path.len() - 1];
-        let (r, c) = tip;
-        for (dr, dc) in CARDINAL_DIRECTIONS {
-            let (nr, nc) = (r + dr, c + dc);
-            if !visited.contains(&(nr, nc)) && 0 <= nr && nr <= end_r && 0 <= nc && nc <= end_c {
-                let mut neighbor_path = path.path.clone();
-                neighbor_path.push((nr, nc));
-                let (row_tile, row_offset) = (nr / rows, nr % rows);
-                let (col_tile, col_offset) = (nc / columns, nc % columns);
-                let neighbor_risk =
-                    map[row_offset as usize][col_offset as usize] as i32 + row_tile + col_tile;
-                let risk = if neighbor_risk > 9 {
-                    neighbor_risk % 10 + 1
+    fn path(&self, previous: &[usize], end: usize, risk: Risk) -> Path {
+        let mut positions: Vec<_> = std::iter::successors(Some(end), |&index| {
+            Some(previous[index]).filter(|&index| index != usize::MAX)
+        })
+        .map(|index| self.position(index))
+        .collect();
+        positions.reverse();
+        Path { positions, risk }
+    }
+
+    /// The cave one row per line, showing the risks along `path` and `.` everywhere else.
+    pub fn render(&self, path: &Path) -> String {
+        let mut on_path = Grid::filled(self.rows(), self.columns(), false);
+        for &position in &path.positions {
+            on_path[position] = true;
+        }
+        on_path
+            .iter()
+            .map(|(position, &on_path)| {
+                let c = if on_path {
+                    char::from_digit(self.risk(position), 10).unwrap_or('?')
                 } else {
-                    neighbor_risk
-                };
-                let neighbor = Path {
-                    path: neighbor_path,
-                    risk: path.risk + risk as u32,
+                    '.'
                 };
-                if nr == end_r && nc == end_c {
-                    return neighbor;
+                if position.1 + 1 == self.columns() {
+                    format!("{c}\n")
+                } else {
+                    c.to_string()
                 }
-                frontier.push(neighbor);
-                visited.insert((nr, nc));
-            }
-        }
+            })
+            .collect()
     }
-    panic!("we should have returned the end path by now")
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(15, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let map = parse_input(lines);
-    let best_path = find_best_path(&map);
-    print_path(&map, &best_path);
-    println!("{}", best_path.risk);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    /// Every position along the path, from the top left to the bottom right.
+    pub positions: Vec<Position>,
+    /// The total risk of every position entered, which leaves out the first.
+    pub risk: Risk,
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(15, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    let map = parse_input(lines);
-    let best_path = find_best_path_tiled(&map);
-    print_path(&map, &best_path);
-    println!("{}", best_path.risk);
+pub fn part_one(map: &RiskMap) -> Result<Risk> {
+    Ok(Cave::new(map, 1)?.safest_path()?.risk)
 }
 
-fn print_path(map: &Vec<Vec<u32>>, path: &Path) {
-    let rows = map.len();
-    let columns = map[0].len();
-    let mut text = String::new();
-    for row in 0..(rows * 5) {
-        for col in 0..(columns * 5) {
-            if path.path.contains(&(row as i32, col as i32)) {
-                let (row_tile, row_offset) = (row / rows, row % rows);
-                let (col_tile, col_offset) = (col / columns, col % columns);
-                let neighbor_risk =
-                    map[row_offset as usize][col_offset as usize] as usize + row_tile + col_tile;
-                let risk = if neighbor_risk > 9 {
-                    neighbor_risk % 10 + 1
-                } else {
-                    neighbor_risk
-                };
-                text.extend(std::iter::once(risk.to_string()));
-            } else {
-                text.extend(std::iter::once('.'));
+pub fn part_two(map: &RiskMap) -> Result<Risk> {
+    Ok(Cave::new(map, 5)?.safest_path()?.risk)
+}
+
+/// The least total risk found by relaxing every position against its neighbors until nothing
+/// improves, which is slow but shares nothing with the search.
+#[cfg(test)]
+fn relaxed_risk(map: &RiskMap) -> Risk {
+    let mut total = Grid::filled(map.rows(), map.columns(), Risk::MAX);
+    total[(0, 0)] = 0;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for position in map.positions() {
+            for neighbor in map.neighbors4(position) {
+                let via = total[neighbor].saturating_add(map[position]);
+                if via < total[position] {
+                    total[position] = via;
+                    changed = true;
+                }
             }
         }
-        text.extend(std::iter::once('\n'))
     }
-    println!("{}", text);
+    total[(map.rows() - 1, map.columns() - 1)]
+}
+
+/// The full map for a cave of `tiles` by `tiles` tiles, written out tile by tile.
+#[cfg(test)]
+fn tile(map: &RiskMap, tiles: Risk) -> RiskMap {
+    let rows = (0..tiles)
+        .flat_map(|row_tile| {
+            map.row_slices().map(move |row| {
+                (0..tiles)
+                    .flat_map(|col_tile| {
+                        row.iter()
+                            .map(move |&risk| (risk + row_tile + col_tile - 1) % 9 + 1)
+                    })
+                    .collect()
+            })
+        })
+        .collect();
+    Grid::from_rows(rows).unwrap()
+}
+
+aoc_core::example_tests!(crate::YEAR, 15);
+
+#[test]
+fn solvers_agree() {
+    use aoc_core::differential::{check_agreement, digit_grid, Solver};
+    let grids = || digit_grid(1..12, 1..12, 1..=9);
+    check_agreement(
+        crate::YEAR,
+        15,
+        64,
+        grids(),
+        &[
+            Solver::new("search", |input| part_one(&parse(input)?)),
+            Solver::new("relaxation", |input| {
+                Ok::<_, Report>(relaxed_risk(&parse(input)?))
+            }),
+        ],
+    );
+    check_agreement(
+        crate::YEAR,
+        15,
+        64,
+        grids(),
+        &[
+            Solver::new("tiled search", |input| part_two(&parse(input)?)),
+            Solver::new("search of the written out map", |input| {
+                part_one(&tile(&parse(input)?, 5))
+            }),
+            Solver::new("relaxation of the written out map", |input| {
+                Ok::<_, Report>(relaxed_risk(&tile(&parse(input)?, 5)))
+            }),
+        ],
+    );
 }
 
 #[test]
 fn example() {
-    let example = r#"1163751742
-1381373672
-2136511328
-3694931569
-7463417111
-1319128137
-1359912421
-3125421639
-1293138521
-2311944581"#;
-    let lines = example.split("\n").map(|s| s.to_string()).collect();
-    let map = parse_input(lines);
-    let best_path = find_best_path(&map);
-    assert_eq!(best_path.risk, 40);
-    let best_path = find_best_path_tiled(&map);
-    print_path(&map, &best_path);
-    assert_eq!(best_path.risk, 315);
+    let map = parse(&read_example(15, 1).unwrap()).unwrap();
+    for tiles in 1..=5 {
+        let cave = Cave::new(&map, tiles).unwrap();
+        let path = cave.safest_path().unwrap();
+        assert_eq!(path.risk, relaxed_risk(&tile(&map, tiles as Risk)));
+        assert_eq!(path.positions.first(), Some(&(0, 0)));
+        assert_eq!(
+            path.positions.last(),
+            Some(&(10 * tiles - 1, 10 * tiles - 1))
+        );
+        let entered: Risk = path.positions[1..].iter().map(|&p| cave.risk(p)).sum();
+        assert_eq!(entered, path.risk);
+    }
+
+    let map = parse("19\n11\n").unwrap();
+    let cave = Cave::new(&map, 1).unwrap();
+    assert_eq!(cave.render(&cave.safest_path().unwrap()), "1.\n11\n");
+    let cave = Cave::new(&map, 2).unwrap();
+    assert_eq!(cave.risk((0, 3)), 1);
+    assert_eq!(
+        cave.render(&cave.safest_path().unwrap()),
+        "1...\n1122\n...2\n...3\n"
+    );
+    assert!(Cave::new(&map, 0).is_err());
+    assert!(part_one(&parse("").unwrap()).is_err());
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(15).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 361);
+    assert_eq!(part_two(&input).unwrap(), 2838);
 }
@@ -1,139 +1,309 @@
 use crate::utils::*;
-use std::collections::VecDeque;
+use aoc_core::parse::{Cursor, LineError, ParseError};
+use eyre::{ContextCompat, Report};
+use std::fmt::Display;
 
-#[derive(PartialEq, Eq, Debug)]
-struct Literal {
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Literal {
     version: u64,
     ty: u64,
     value: u64,
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct Operator {
+/// How an operator says where its subpackets end.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LengthType {
+    /// Length type 0: the total number of bits in the subpackets.
+    Bits,
+    /// Length type 1: the number of subpackets.
+    Count,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Operator {
     version: u64,
     ty: u64,
+    length_type: LengthType,
     subpackets: Vec<Packet>,
 }
 
-#[derive(PartialEq, Eq, Debug)]
-enum Packet {
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Packet {
     Lit(Literal),
     Op(Operator),
 }
 
-#[derive(Debug)]
-struct BitStream {
-    buffer: VecDeque<bool>,
-    characters: VecDeque<char>,
+/// Why a transmission could not be decoded, with the bit it went wrong at.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    Truncated {
+        position: usize,
+        wanted: usize,
+        available: usize,
+    },
+    BadHexDigit {
+        position: usize,
+        digit: char,
+    },
+    OperandCount {
+        position: usize,
+        ty: u64,
+        found: usize,
+    },
+    LiteralOverflow {
+        position: usize,
+    },
+}
+
+impl DecodeError {
+    fn position(&self) -> usize {
+        match self {
+            DecodeError::Truncated { position, .. }
+            | DecodeError::BadHexDigit { position, .. }
+            | DecodeError::OperandCount { position, .. }
+            | DecodeError::LiteralOverflow { position } => *position,
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bit {}: ", self.position())?;
+        match self {
+            DecodeError::Truncated {
+                wanted, available, ..
+            } => write!(
+                f,
+                "needed {wanted} bits, but the transmission ends after {available}"
+            ),
+            DecodeError::BadHexDigit { digit, .. } => write!(f, "{digit:?} is not a hex digit"),
+            DecodeError::OperandCount { ty, found, .. } => {
+                write!(f, "comparison of type {ty} needs 2 operands, found {found}")
+            }
+            DecodeError::LiteralOverflow { .. } => write!(f, "literal does not fit in 64 bits"),
+        }
+    }
 }
 
-impl BitStream {
-    fn is_empty(&self) -> bool {
-        self.buffer.is_empty() && self.characters.is_empty()
+impl std::error::Error for DecodeError {}
+
+/// Points at the hex digit holding the bit the transmission went wrong at.
+impl LineError for DecodeError {
+    fn locate(self, line: &str) -> ParseError {
+        ParseError::at(line, self.position() / 4, self.to_string())
     }
-    // fn clear_buffer(&mut self) {
-    //     self.buffer.clear();
-    // }
 }
 
-impl From<Vec<bool>> for BitStream {
-    fn from(v: Vec<bool>) -> Self {
-        BitStream {
-            buffer: v.into(),
-            characters: Default::default(),
+/// Reads a transmission a few bits at a time, from the bytes its hex digits spell out.
+struct BitReader {
+    bytes: Vec<u8>,
+    position: usize,
+    /// The bit reading stops at, which is moved in while reading the subpackets of an operator
+    /// with a length in bits so that they cannot run past it.
+    end: usize,
+}
+
+impl BitReader {
+    fn from_hex(hex: &str) -> Result<Self, DecodeError> {
+        let mut bytes = vec![0; hex.len().div_ceil(2)];
+        for (index, digit) in hex.chars().enumerate() {
+            let value = digit.to_digit(16).ok_or(DecodeError::BadHexDigit {
+                position: index * 4,
+                digit,
+            })?;
+            bytes[index / 2] |= (value as u8) << if index % 2 == 0 { 4 } else { 0 };
         }
+        Ok(BitReader {
+            end: hex.len() * 4,
+            bytes,
+            position: 0,
+        })
+    }
+
+    /// Reads the next `width` bits as a number, most significant first.
+    fn read(&mut self, width: usize) -> Result<u64, DecodeError> {
+        if self.position + width > self.end {
+            return Err(DecodeError::Truncated {
+                position: self.position,
+                wanted: width,
+                available: self.end - self.position,
+            });
+        }
+        let mut value = 0;
+        let mut remaining = width;
+        while remaining > 0 {
+            let offset = self.position % 8;
+            let taken = remaining.min(8 - offset);
+            let bits =
+                (self.bytes[self.position / 8] as u64 >> (8 - offset - taken)) & ((1 << taken) - 1);
+            value = (value << taken) | bits;
+            self.position += taken;
+            remaining -= taken;
+        }
+        Ok(value)
+    }
+
+    fn read_bit(&mut self) -> Result<bool, DecodeError> {
+        Ok(self.read(1)? == 1)
     }
 }
 
-impl Iterator for BitStream {
-    type Item = bool;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(bit) = self.buffer.pop_front() {
-            Some(bit)
-        } else if let Some(c) = self.characters.pop_front() {
-            let bits = c.to_digit(16).unwrap();
-            // println!("{} = '{}'", c, format!("{:04b}", bits));
-            for c in format!("{:04b}", bits).chars() {
-                // println!("'{}'", c);
-                self.buffer.push_back(match c {
-                    '0' => false,
-                    '1' => true,
-                    _ => unreachable!(),
+fn decode_packet(reader: &mut BitReader) -> Result<Packet, DecodeError> {
+    let start = reader.position;
+    let version = reader.read(3)?;
+    let ty = reader.read(3)?;
+    if ty == 4 {
+        let mut value = 0u64;
+        loop {
+            let more = reader.read_bit()?;
+            if value >> 60 != 0 {
+                return Err(DecodeError::LiteralOverflow { position: start });
+            }
+            value = (value << 4) | reader.read(4)?;
+            if !more {
+                break;
+            }
+        }
+        return Ok(Packet::Lit(Literal { version, ty, value }));
+    }
+
+    let mut subpackets = Vec::new();
+    let length_type = if reader.read_bit()? {
+        LengthType::Count
+    } else {
+        LengthType::Bits
+    };
+    match length_type {
+        LengthType::Count => {
+            let count = reader.read(11)?;
+            for _ in 0..count {
+                subpackets.push(decode_packet(reader)?);
+            }
+        }
+        LengthType::Bits => {
+            let length = reader.read(15)? as usize;
+            if reader.position + length > reader.end {
+                return Err(DecodeError::Truncated {
+                    position: reader.position,
+                    wanted: length,
+                    available: reader.end - reader.position,
                 });
             }
-            self.buffer.pop_front()
-        } else {
-            None
+            let outer_end = std::mem::replace(&mut reader.end, reader.position + length);
+            while reader.position < reader.end {
+                subpackets.push(decode_packet(reader)?);
+            }
+            reader.end = outer_end;
         }
     }
+    if (5..=7).contains(&ty) && subpackets.len() != 2 {
+        return Err(DecodeError::OperandCount {
+            position: start,
+            ty,
+            found: subpackets.len(),
+        });
+    }
+    Ok(Packet::Op(Operator {
+        version,
+        ty,
+        length_type,
+        subpackets,
+    }))
+}
+
+/// Decodes the packet a hex transmission holds, ignoring any padding after it.
+pub fn decode(hex: &str) -> Result<Packet, DecodeError> {
+    decode_packet(&mut BitReader::from_hex(hex)?)
 }
 
-fn parse_line(line: &str) -> BitStream {
-    BitStream {
-        buffer: Default::default(),
-        characters: line.chars().collect(),
+/// Bits in transmission order, for encoding packets.
+#[derive(Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    /// Writes the lowest `width` bits of `value`, most significant first.
+    fn write(&mut self, value: u64, width: u32, what: &str) -> Result<()> {
+        if width < 64 && value >> width != 0 {
+            return Err(Report::msg(format!(
+                "{what} {value} does not fit in {width} bits"
+            )));
+        }
+        self.bits
+            .extend((0..width).rev().map(|shift| (value >> shift) & 1 == 1));
+        Ok(())
+    }
+
+    /// The bits as hex, padded with zeros to a whole number of digits.
+    fn to_hex(&self) -> String {
+        self.bits
+            .chunks(4)
+            .map(|nibble| {
+                let value = (0..4).fold(0, |value, i| {
+                    (value << 1) | nibble.get(i).copied().unwrap_or(false) as u32
+                });
+                char::from_digit(value, 16)
+                    .expect("a nibble is a hex digit")
+                    .to_ascii_uppercase()
+            })
+            .collect()
     }
 }
 
-fn bools_to_bits(bools: impl Iterator<Item = bool>) -> u64 {
-    let bools: Vec<bool> = bools.collect();
-    bools
-        .into_iter()
-        .rev()
-        .enumerate()
-        .map(|(idx, b)| (b as u64) << idx)
-        .sum()
-}
-
-fn parse_stream(stream: &mut BitStream) -> Packet {
-    // println!("entering new parsing with {:?}", stream);
-    let version = bools_to_bits(stream.by_ref().take(3));
-    let ty = bools_to_bits(stream.by_ref().take(3));
-    match ty {
-        4 => {
-            let mut bools = Vec::new();
-            // let mut taken = 6;
-            while let Some(b) = stream.next() {
-                // taken += 5;
-                if b {
-                    bools.extend(stream.by_ref().take(4));
-                } else {
-                    bools.extend(stream.by_ref().take(4));
-                    break;
-                }
+fn encode_into(packet: &Packet, writer: &mut BitWriter) -> Result<()> {
+    match packet {
+        Packet::Lit(literal) => {
+            if literal.ty != 4 {
+                return Err(Report::msg(format!(
+                    "a literal has type 4, not {}",
+                    literal.ty
+                )));
+            }
+            writer.write(literal.version, 3, "version")?;
+            writer.write(literal.ty, 3, "type")?;
+            // groups of four bits, most significant first, with at least one group for zero
+            let groups = (64 - literal.value.leading_zeros()).div_ceil(4).max(1);
+            for group in (0..groups).rev() {
+                writer.write((group > 0) as u64, 1, "continuation bit")?;
+                writer.write((literal.value >> (group * 4)) & 0xF, 4, "literal group")?;
             }
-            let value = bools_to_bits(bools.into_iter());
-            // let alignment = taken % 4;
-            // println!("{}, {:?}", alignment, stream);
-            // if alignment != 0 {
-            //     let _drop: Vec<bool> = stream.by_ref().take(4 - alignment).collect();
-            // }
-            // println!("{:?}", stream);
-            Packet::Lit(Literal { version, ty, value })
-        },
-        _ => {
-            let mut subpackets = Vec::new();
-            if stream.next().unwrap() {
-                let number_of_subpackets = bools_to_bits(stream.by_ref().take(11));
-                for _ in 0..number_of_subpackets {
-                    subpackets.push(parse_stream(stream));
+        }
+        Packet::Op(operator) => {
+            if operator.ty == 4 {
+                return Err(Report::msg("type 4 is for literals, not operators"));
+            }
+            writer.write(operator.version, 3, "version")?;
+            writer.write(operator.ty, 3, "type")?;
+            match operator.length_type {
+                LengthType::Bits => {
+                    let mut subpackets = BitWriter::default();
+                    for subpacket in &operator.subpackets {
+                        encode_into(subpacket, &mut subpackets)?;
+                    }
+                    writer.write(0, 1, "length type")?;
+                    writer.write(subpackets.bits.len() as u64, 15, "subpacket length")?;
+                    writer.bits.extend(subpackets.bits);
                 }
-            } else {
-                let number_of_bits = bools_to_bits(stream.by_ref().take(15)) as usize;
-                let bits: Vec<bool> = stream.by_ref().take(number_of_bits).collect();
-                let mut bits: BitStream = bits.into();
-                while !bits.is_empty() {
-                    subpackets.push(parse_stream(&mut bits));
+                LengthType::Count => {
+                    writer.write(1, 1, "length type")?;
+                    writer.write(operator.subpackets.len() as u64, 11, "subpacket count")?;
+                    for subpacket in &operator.subpackets {
+                        encode_into(subpacket, writer)?;
+                    }
                 }
             }
-            Packet::Op(Operator {
-                version,
-                ty,
-                subpackets,
-            })
         }
     }
+    Ok(())
+}
+
+/// The hex transmission for `packet`, using each operator's own length type. Fails if a field
+/// does not fit in its bits, such as a version above 7 or more than 2047 subpackets.
+pub fn encode(packet: &Packet) -> Result<String> {
+    let mut writer = BitWriter::default();
+    encode_into(packet, &mut writer)?;
+    Ok(writer.to_hex())
 }
 
 fn version_sum(packet: &Packet) -> u64 {
@@ -146,189 +316,363 @@ fn version_sum(packet: &Packet) -> u64 {
     }
 }
 
-fn evaluate(packet: &Packet) -> u64 {
+/// Evaluates both operands of a comparison operator with `compare`.
+fn compare(operator: &Operator, compare: impl Fn(u64, u64) -> bool) -> Result<u64> {
+    if let [first, second] = &*operator.subpackets {
+        Ok(compare(evaluate(first)?, evaluate(second)?) as u64)
+    } else {
+        Err(Report::msg(format!(
+            "comparison of type {} needs 2 operands, found {}",
+            operator.ty,
+            operator.subpackets.len()
+        )))
+    }
+}
+
+fn evaluate(packet: &Packet) -> Result<u64> {
     match packet {
-        Packet::Lit(literal) => literal.value,
+        Packet::Lit(literal) => Ok(literal.value),
         Packet::Op(operator) => {
+            let values = operator.subpackets.iter().map(evaluate);
             match operator.ty {
-                0 => {
-                    // sum
-                    operator.subpackets.iter().map(evaluate).sum()
-                }
-                1 => {
-                    // product
-                    operator.subpackets.iter().map(evaluate).product()
-                }
-                2 => {
-                    // minimum
-                    operator.subpackets.iter().map(evaluate).min().unwrap()
-                }
-                3 => {
-                    // max
-                    operator.subpackets.iter().map(evaluate).max().unwrap()
-                }
-                5 => {
-                    // greater than
-                    if let [first, second] = &*operator.subpackets {
-                        if evaluate(first) > evaluate(second) {
-                            1
-                        } else {
-                            0
-                        }
-                    } else {
-                        panic!("bad pattern match for gt")
-                    }
-                }
-                6 => {
-                    // less than
-                    if let [first, second] = &*operator.subpackets {
-                        if evaluate(first) < evaluate(second) {
-                            1
-                        } else {
-                            0
-                        }
-                    } else {
-                        panic!("bad pattern match for gt")
-                    }
+                // sum
+                0 => values.sum(),
+                // product
+                1 => values.product(),
+                // minimum
+                2 => values
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .min()
+                    .wrap_err("minimum of no operands"),
+                // max
+                3 => values
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .max()
+                    .wrap_err("maximum of no operands"),
+                // greater than
+                5 => compare(operator, |a, b| a > b),
+                // less than
+                6 => compare(operator, |a, b| a < b),
+                // equal to
+                7 => compare(operator, |a, b| a == b),
+                ty => Err(Report::msg(format!("unknown operator type {}", ty))),
+            }
+        }
+    }
+}
+
+/// The operator types, by the symbols they are written with in expressions.
+const SYMBOLS: [(u64, &str); 7] = [
+    (0, "+"),
+    (1, "*"),
+    (2, "min"),
+    (3, "max"),
+    (5, ">"),
+    (6, "<"),
+    (7, "="),
+];
+
+/// Writes the packet as an S-expression, like `(+ 1 (* 2 3))`. Versions and length types are
+/// not shown.
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Lit(literal) => write!(f, "{}", literal.value),
+            Packet::Op(operator) => {
+                match SYMBOLS.iter().find(|(ty, _)| *ty == operator.ty) {
+                    Some((_, symbol)) => write!(f, "({symbol}")?,
+                    None => write!(f, "(op{}", operator.ty)?,
                 }
-                7 => {
-                    // equal to
-                    if let [first, second] = &*operator.subpackets {
-                        if evaluate(first) == evaluate(second) {
-                            1
-                        } else {
-                            0
-                        }
-                    } else {
-                        panic!("bad pattern match for gt")
-                    }
+                for subpacket in &operator.subpackets {
+                    write!(f, " {subpacket}")?;
                 }
-                _ => panic!("ahhh!"),
+                write!(f, ")")
             }
         }
     }
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(16, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    assert!(lines.len() == 1);
-    let mut bits = parse_line(&lines[0]);
-    let packet = parse_stream(&mut bits);
-    println!("{}", version_sum(&packet));
+fn parse_term(cursor: &mut Cursor) -> std::result::Result<Packet, ParseError> {
+    cursor.skip_whitespace();
+    if !cursor.eat("(") {
+        return Ok(Packet::Lit(Literal {
+            version: 0,
+            ty: 4,
+            value: cursor.number()?,
+        }));
+    }
+    cursor.skip_whitespace();
+    let at_symbol = cursor.clone();
+    let symbol = cursor.take_while(|c| !c.is_whitespace() && c != '(' && c != ')');
+    let (ty, _) = SYMBOLS
+        .iter()
+        .find(|(_, known)| *known == symbol)
+        .ok_or_else(|| at_symbol.error(format!("unknown operator {symbol:?}")))?;
+    let mut subpackets = Vec::new();
+    loop {
+        cursor.skip_whitespace();
+        if cursor.eat(")") {
+            break;
+        }
+        if cursor.is_empty() {
+            return Err(cursor.error("expected \")\""));
+        }
+        subpackets.push(parse_term(cursor)?);
+    }
+    Ok(Packet::Op(Operator {
+        version: 0,
+        ty: *ty,
+        length_type: LengthType::Count,
+        subpackets,
+    }))
+}
+
+/// Parses an S-expression written the way packets display, giving every packet version 0 and
+/// every operator the subpacket count length type.
+pub fn parse_expression(text: &str) -> std::result::Result<Packet, ParseError> {
+    let mut cursor = Cursor::new(text);
+    let packet = parse_term(&mut cursor)?;
+    cursor.skip_whitespace();
+    cursor.end()?;
+    Ok(packet)
+}
+
+pub fn parse(input: &str) -> Result<Packet> {
+    let line = input.trim();
+    Ok(decode(line).map_err(|error| error.locate(line))?)
+}
+
+pub fn part_one(packet: &Packet) -> Result<u64> {
+    Ok(version_sum(packet))
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(16, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    assert!(lines.len() == 1);
-    let mut bits = parse_line(&lines[0]);
-    let packet = parse_stream(&mut bits);
-    println!("{}", evaluate(&packet));
+pub fn part_two(packet: &Packet) -> Result<u64> {
+    evaluate(packet)
 }
 
+aoc_core::example_tests!(crate::YEAR, 16);
+
 #[test]
 fn example() {
-    let examples = vec![
-        (
-            "D2FE28",
-            Packet::Lit(Literal {
-                version: 6,
-                ty: 4,
-                value: 2021,
-            }),
-            6,
-        ),
-        (
-            "38006F45291200",
-            Packet::Op(Operator {
+    // the packets the first few examples decode to
+    let expected = vec![
+        Packet::Lit(Literal {
+            version: 6,
+            ty: 4,
+            value: 2021,
+        }),
+        Packet::Op(Operator {
+            version: 1,
+            ty: 6,
+            length_type: LengthType::Bits,
+            subpackets: vec![
+                Packet::Lit(Literal {
+                    version: 6,
+                    ty: 4,
+                    value: 10,
+                }),
+                Packet::Lit(Literal {
+                    version: 2,
+                    ty: 4,
+                    value: 20,
+                }),
+            ],
+        }),
+        Packet::Op(Operator {
+            version: 7,
+            ty: 3,
+            length_type: LengthType::Count,
+            subpackets: vec![
+                Packet::Lit(Literal {
+                    version: 2,
+                    ty: 4,
+                    value: 1,
+                }),
+                Packet::Lit(Literal {
+                    version: 4,
+                    ty: 4,
+                    value: 2,
+                }),
+                Packet::Lit(Literal {
+                    version: 1,
+                    ty: 4,
+                    value: 3,
+                }),
+            ],
+        }),
+        Packet::Op(Operator {
+            version: 4,
+            ty: 2,
+            length_type: LengthType::Count,
+            subpackets: vec![Packet::Op(Operator {
                 version: 1,
-                ty: 6,
-                subpackets: vec![
-                    Packet::Lit(Literal {
-                        version: 6,
-                        ty: 4,
-                        value: 10,
-                    }),
-                    Packet::Lit(Literal {
-                        version: 2,
-                        ty: 4,
-                        value: 20,
-                    }),
-                ],
-            }),
-            9,
-        ),
-        (
-            "EE00D40C823060",
-            Packet::Op(Operator {
-                version: 7,
-                ty: 3,
-                subpackets: vec![
-                    Packet::Lit(Literal {
-                        version: 2,
-                        ty: 4,
-                        value: 1,
-                    }),
-                    Packet::Lit(Literal {
-                        version: 4,
-                        ty: 4,
-                        value: 2,
-                    }),
-                    Packet::Lit(Literal {
-                        version: 1,
-                        ty: 4,
-                        value: 3,
-                    }),
-                ],
-            }),
-            14,
-        ),
-        (
-            "8A004A801A8002F478",
-            Packet::Op(Operator {
-                version: 4,
                 ty: 2,
+                length_type: LengthType::Count,
                 subpackets: vec![Packet::Op(Operator {
-                    version: 1,
+                    version: 5,
                     ty: 2,
-                    subpackets: vec![Packet::Op(Operator {
-                        version: 5,
-                        ty: 2,
-                        subpackets: vec![Packet::Lit(Literal {
-                            version: 6,
-                            ty: 4,
-                            value: 15,
-                        })],
+                    length_type: LengthType::Bits,
+                    subpackets: vec![Packet::Lit(Literal {
+                        version: 6,
+                        ty: 4,
+                        value: 15,
                     })],
                 })],
-            }),
-            16,
-        ),
-        // "620080001611562C8802118E34",
-        // "C0015000016115A2E0802F182340",
-        // "A0016C880162017C3686B18A3D4780",
+            })],
+        }),
     ];
-    for (line, expected, expected_version_sum) in examples {
-        let mut bits = parse_line(line);
-        let packet = parse_stream(&mut bits);
-        assert_eq!(packet, expected);
-        assert_eq!(version_sum(&packet), expected_version_sum);
+    for (number, expected) in (1..).zip(expected) {
+        assert_eq!(parse(&read_example(16, number).unwrap()).unwrap(), expected);
     }
 
-    let examples = vec![
-        ("C200B40A82", 3),
-        ("04005AC33890", 54),
-        ("880086C3E88112", 7),
-        ("CE00C43D881120", 9),
-        ("D8005AC2A8F0", 1),
-        ("F600BC2D8F", 0),
-        ("9C005AC2F8F0", 0),
-        ("9C0141080250320F1802104A08", 1)
+    // the expressions the rest of the examples describe
+    let expressions = [
+        "(+ 1 2)",
+        "(* 6 9)",
+        "(min 7 8 9)",
+        "(max 7 8 9)",
+        "(< 5 15)",
+        "(> 5 15)",
+        "(= 5 15)",
+        "(= (+ 1 3) (* 2 2))",
     ];
-    for (line, expected) in examples {
-        let mut bits = parse_line(line);
-        let packet = parse_stream(&mut bits);
-        assert_eq!(evaluate(&packet), expected);
+    for (number, expected) in (5..).zip(expressions) {
+        let packet = parse(&read_example(16, number).unwrap()).unwrap();
+        assert_eq!(packet.to_string(), expected);
+        assert_eq!(
+            evaluate(&parse_expression(expected).unwrap()).unwrap(),
+            evaluate(&packet).unwrap()
+        );
+    }
+}
+
+#[test]
+fn encodes_as_transmitted() {
+    // transmissions are padded out with zeros, which encoding leaves off
+    for number in 1..=12 {
+        let transmission = read_example(16, number).unwrap();
+        let transmission = transmission.trim();
+        let encoded = encode(&parse(transmission).unwrap()).unwrap();
+        let (start, padding) = transmission.split_at(encoded.len().min(transmission.len()));
+        assert_eq!(start, encoded, "example {number}");
+        assert!(padding.chars().all(|c| c == '0'), "example {number}");
     }
 }
+
+#[test]
+fn reports_where_decoding_fails() {
+    // the third group of the literal 2021 would start at bit 16
+    assert_eq!(
+        decode("D2FE"),
+        Err(DecodeError::Truncated {
+            position: 16,
+            wanted: 1,
+            available: 0
+        })
+    );
+    assert_eq!(
+        decode("D2GE28"),
+        Err(DecodeError::BadHexDigit {
+            position: 8,
+            digit: 'G'
+        })
+    );
+    // the comparison follows the sum's 18 bit header and the 11 bit literal 1
+    let three_operands = encode(&parse_expression("(+ 1 (< 1 2 3))").unwrap()).unwrap();
+    assert_eq!(
+        decode(&three_operands),
+        Err(DecodeError::OperandCount {
+            position: 29,
+            ty: 6,
+            found: 3
+        })
+    );
+    // a literal of seventeen groups has more than 64 bits
+    let mut long_literal = BitWriter::default();
+    long_literal.write(0b100, 6, "header").unwrap();
+    for group in 0..17 {
+        long_literal
+            .write((group < 16) as u64, 1, "continuation bit")
+            .unwrap();
+        long_literal.write(0xF, 4, "literal group").unwrap();
+    }
+    assert_eq!(
+        decode(&long_literal.to_hex()),
+        Err(DecodeError::LiteralOverflow { position: 0 })
+    );
+
+    let error = parse("D2GE28\n")
+        .unwrap_err()
+        .downcast::<ParseError>()
+        .unwrap();
+    assert_eq!(
+        (error.column, error.message.as_str()),
+        (3, "bit 8: 'G' is not a hex digit")
+    );
+}
+
+#[test]
+fn rejects_bad_expressions() {
+    let error = parse_expression("(+ 1 (avg 2 3))").unwrap_err();
+    assert_eq!(
+        (error.column, error.message.as_str()),
+        (7, "unknown operator \"avg\"")
+    );
+    assert!(parse_expression("(+ 1 2").is_err());
+    assert!(parse_expression("(+ 1 2))").is_err());
+}
+
+/// Valid packet trees with any versions, operator types and length types.
+#[cfg(test)]
+fn packets() -> impl proptest::strategy::Strategy<Value = Packet> {
+    use proptest::prelude::*;
+    let literal = (0..8u64, any::<u64>()).prop_map(|(version, value)| {
+        Packet::Lit(Literal {
+            version,
+            ty: 4,
+            value,
+        })
+    });
+    literal.prop_recursive(4, 48, 5, |subpacket| {
+        let operator = move |types: std::ops::Range<u64>, operands: std::ops::Range<usize>| {
+            let length_type = prop_oneof![Just(LengthType::Bits), Just(LengthType::Count)];
+            let subpackets = proptest::collection::vec(subpacket.clone(), operands);
+            (0..8u64, types, length_type, subpackets).prop_map(
+                |(version, ty, length_type, subpackets)| {
+                    Packet::Op(Operator {
+                        version,
+                        ty,
+                        length_type,
+                        subpackets,
+                    })
+                },
+            )
+        };
+        // comparisons always have exactly two operands
+        prop_oneof![operator(0..4, 1..5), operator(5..8, 2..3)]
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn decodes_what_it_encodes(packet in packets()) {
+        let hex = encode(&packet).unwrap();
+        proptest::prop_assert_eq!(parse(&hex).unwrap(), packet);
+    }
+
+    #[test]
+    fn parses_what_it_displays(packet in packets()) {
+        let expression = packet.to_string();
+        proptest::prop_assert_eq!(parse_expression(&expression).unwrap().to_string(), expression);
+    }
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(16).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 904);
+    assert_eq!(part_two(&input).unwrap(), 200476472872);
+}
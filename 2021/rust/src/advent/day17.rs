@@ -1,21 +1,182 @@
-use std::cmp::max;
+use std::cmp::{max, min};
 
-use regex::Regex;
+use aoc_core::scan;
 
 use crate::utils::*;
+use eyre::{ContextCompat, Report};
 
 #[derive(Debug)]
-struct TargetArea {
+pub struct TargetArea {
     x: (i32, i32),
     y: (i32, i32),
 }
 
+/// A launch velocity that lands the probe in the target area.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Shot {
+    pub velocity: (i64, i64),
+    /// The highest the probe gets, which is the launcher's height for shots aimed down.
+    pub apex: i64,
+    /// The first step that ends inside the target area.
+    pub first_step: i64,
+}
+
+pub fn parse(input: &str) -> Result<TargetArea> {
+    let (x_min, x_max, y_min, y_max) = scan!(
+        input.trim(),
+        "target area: x={}..{}, y={}..{}" => i32, i32, i32, i32
+    )?;
+    Ok(TargetArea {
+        x: (x_min, x_max),
+        y: (y_min, y_max),
+    })
+}
+
+/// The height after `n` steps of a probe launched upwards at `vy`. The same triangular number
+/// formula gives the distance travelled sideways, until drag stops the probe.
+fn height(vy: i64, n: i64) -> i64 {
+    n * vy - n * (n - 1) / 2
+}
+
+/// The window of steps, counting back before the launch too, at which the height is at least
+/// `floor`. The height is a parabola in `n` peaking at steps `vy` and `vy + 1`, so the window is
+/// between the roots of `n² - (2vy + 1)n + 2 floor = 0`.
+fn steps_at_least(vy: i64, floor: i64) -> Option<(i64, i64)> {
+    if height(vy, vy) < floor {
+        return None;
+    }
+    let b = 2 * vy + 1;
+    let root = (b * b - 8 * floor).isqrt();
+    // the integer square root can leave either end a step out, so settle them on each side of
+    // the peak, where the height only rises or only falls
+    let mut first = (b - root).div_euclid(2).min(vy);
+    while height(vy, first) < floor {
+        first += 1;
+    }
+    while height(vy, first - 1) >= floor {
+        first -= 1;
+    }
+    let mut last = (b + root).div_euclid(2).max(vy + 1);
+    while height(vy, last) < floor {
+        last -= 1;
+    }
+    while height(vy, last + 1) >= floor {
+        last += 1;
+    }
+    Some((first, last))
+}
+
+/// The windows of steps at which a probe launched upwards at `vy` is level with `(low, high)`:
+/// one on the way up and one on the way down, or a single one if it never rises above `high`.
+fn y_windows(vy: i64, (low, high): (i64, i64)) -> Vec<(i64, i64)> {
+    let Some((first, last)) = steps_at_least(vy, low) else {
+        return Vec::new();
+    };
+    let windows = match steps_at_least(vy, high + 1) {
+        Some((above, below)) => vec![(first, above - 1), (below + 1, last)],
+        None => vec![(first, last)],
+    };
+    windows
+        .into_iter()
+        .map(|(first, last)| (first.max(1), last))
+        .filter(|(first, last)| first <= last)
+        .collect()
+}
+
+/// The window of steps at which a probe launched sideways at `vx` is in line with `(low, high)`,
+/// lasting forever (`i64::MAX`) if it stalls there. Drag stops it after `|vx|` steps, once it has
+/// travelled the `|vx|`th triangular number.
+fn x_window(vx: i64, (low, high): (i64, i64)) -> Option<(i64, i64)> {
+    if vx < 0 {
+        return x_window(-vx, (-high, -low));
+    }
+    let stall = height(vx, vx);
+    if stall < low {
+        return None;
+    }
+    let first = if low <= 0 {
+        1
+    } else {
+        steps_at_least(vx, low)?.0
+    };
+    let last = if stall <= high {
+        i64::MAX
+    } else {
+        steps_at_least(vx, high + 1)?.0 - 1
+    };
+    (first <= last).then_some((first, last))
+}
+
+/// Every velocity that lands the probe in the target area, wherever that is around the launcher,
+/// found by intersecting the steps at which each horizontal and vertical velocity is in line with
+/// it. Fails if there are infinitely many, which happens when the area spans the launcher's height
+/// and some shot stalls above or below it.
+pub fn shots(target_area: &TargetArea) -> Result<Vec<Shot>> {
+    let xs = (target_area.x.0 as i64, target_area.x.1 as i64);
+    let ys = (target_area.y.0 as i64, target_area.y.1 as i64);
+    let x_windows: Vec<_> = (min(xs.0, 0)..=max(xs.1, 0))
+        .filter_map(|vx| Some((vx, x_window(vx, xs)?)))
+        .collect();
+    let highest_vy = if ys.0 > 0 {
+        // any faster and the first step already overshoots, and the way down mirrors the way up
+        ys.1
+    } else if ys.1 < 0 {
+        // any faster and it falls back past the launcher too fast to stop in the area
+        -ys.0 - 1
+    } else {
+        // every upward shot comes back down through the launcher's height, so only the
+        // sideways windows limit how long a shot can take
+        let last_step = x_windows.iter().map(|(_, (_, last))| *last).max();
+        match last_step {
+            Some(i64::MAX) => {
+                return Err(Report::msg(
+                    "infinitely many shots stall in line with the target area and fall into it",
+                ))
+            }
+            Some(last_step) => ys.1 + last_step,
+            None => 0,
+        }
+    };
+    let mut shots = Vec::new();
+    for vy in min(ys.0, 0)..=highest_vy {
+        let y_windows = y_windows(vy, ys);
+        for &(vx, (x_first, x_last)) in &x_windows {
+            let first_step = y_windows.iter().find_map(|&(y_first, y_last)| {
+                let first = max(x_first, y_first);
+                (first <= min(x_last, y_last)).then_some(first)
+            });
+            if let Some(first_step) = first_step {
+                shots.push(Shot {
+                    velocity: (vx, vy),
+                    apex: height(vy, vy.max(0)),
+                    first_step,
+                });
+            }
+        }
+    }
+    Ok(shots)
+}
+
+pub fn part_one(target_area: &TargetArea) -> Result<i64> {
+    shots(target_area)?
+        .iter()
+        .map(|shot| shot.apex)
+        .max()
+        .wrap_err("no shot lands in the target area")
+}
+
+pub fn part_two(target_area: &TargetArea) -> Result<usize> {
+    Ok(shots(target_area)?.len())
+}
+
+#[cfg(test)]
 #[derive(Debug)]
 struct Probe {
     position: (i32, i32),
     velocity: (i32, i32),
 }
 
+#[cfg(test)]
 impl Probe {
     fn new(x: i32, y: i32) -> Probe {
         Probe {
@@ -25,24 +186,21 @@ impl Probe {
     }
 }
 
-fn parse_input(line: &str) -> TargetArea {
-    let r = Regex::new(r"x=(.+)\.\.(.+), y=(.+)\.\.(.+)").unwrap();
-    let captures = r.captures(&line).unwrap();
-    let x = (captures[1].parse().unwrap(), captures[2].parse().unwrap());
-    let y = (captures[3].parse().unwrap(), captures[4].parse().unwrap());
-    TargetArea { x, y }
-}
-
+#[cfg(test)]
 fn step(probe: Probe) -> Probe {
     Probe {
         position: (
             probe.position.0 + probe.velocity.0,
             probe.position.1 + probe.velocity.1,
         ),
-        velocity: (max(0, probe.velocity.0 - 1), probe.velocity.1 - 1),
+        velocity: (
+            probe.velocity.0 - probe.velocity.0.signum(),
+            probe.velocity.1 - 1,
+        ),
     }
 }
 
+#[cfg(test)]
 fn contains(probe: &Probe, target_area: &TargetArea) -> bool {
     target_area.x.0 <= probe.position.0
         && probe.position.0 <= target_area.x.1
@@ -50,93 +208,42 @@ fn contains(probe: &Probe, target_area: &TargetArea) -> bool {
         && probe.position.1 <= target_area.y.1
 }
 
-fn high_shot(target_area: &TargetArea) -> (i32, i32) {
-    for x in (0..target_area.x.1).rev() {
-        let distance = sum_to_n(x as u32) as i32;
-        if target_area.x.0 <= distance && distance <= target_area.x.1 {
-            return (x, -(1 + target_area.y.0));
-        }
-    }
-    panic!("oops")
-}
-
-fn minimum_x(target_area: &TargetArea) -> i32 {
-    for x in 0..target_area.x.1 {
-        let distance = sum_to_n(x as u32) as i32;
-        if target_area.x.0 <= distance && distance <= target_area.x.1 {
-            return x;
-        }
-    }
-    panic!("oops")
-}
-
-fn maximum_x(target_area: &TargetArea) -> i32 {
-    target_area.x.1
-}
-
-fn minimum_y(target_area: &TargetArea) -> i32 {
-    target_area.y.0
-}
-
-fn maximum_y(target_area: &TargetArea) -> i32 {
-    -(1 + target_area.y.0)
-}
-
-fn count_solutions(target_area: &TargetArea) -> u32 {
-    let mut count = 0;
-    for x in minimum_x(target_area)..=maximum_x(target_area) {
-        for y in minimum_y(target_area)..=maximum_y(target_area){
-            // println!("testing {:?}", (x, y));
-            let mut probe = Probe::new(x, y);
-            while target_area.y.0 < probe.position.1 {
+/// Every shot with a velocity of at most `limit` either way, found by stepping each probe until
+/// it has fallen past the target area.
+#[cfg(test)]
+fn simulated_shots(target_area: &TargetArea, limit: i32) -> Vec<Shot> {
+    let mut shots = Vec::new();
+    for vy in -limit..=limit {
+        for vx in -limit..=limit {
+            let mut probe = Probe::new(vx, vy);
+            let (mut apex, mut first_step) = (0, None);
+            for n in 1.. {
                 probe = step(probe);
-                if contains(&probe, target_area) {
-                    count += 1;
+                apex = max(apex, probe.position.1);
+                if first_step.is_none() && contains(&probe, target_area) {
+                    first_step = Some(n);
+                }
+                if probe.position.1 < target_area.y.0 && probe.velocity.1 < 0 {
                     break;
                 }
             }
+            if let Some(first_step) = first_step {
+                shots.push(Shot {
+                    velocity: (vx as i64, vy as i64),
+                    apex: apex as i64,
+                    first_step,
+                });
+            }
         }
     }
-    count
-}
-
-pub fn part_one() {
-    let ip = problem_input_path(17, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    assert!(lines.len() == 1);
-    let target_area = parse_input(&lines[0]);
-    let (x, y) = high_shot(&target_area);
-    let mut max_y = 0;
-    let mut probe = Probe::new(x, y);
-    while !contains(&probe, &target_area) {
-        probe = step(probe);
-        if probe.position.1 > max_y {
-            max_y = probe.position.1;
-        }
-        if probe.position.1 < target_area.y.0 {
-            panic!("oops")
-        }
-    }
-    println!("{}", max_y);
-}
-
-pub fn part_two() {
-    let ip = problem_input_path(17, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    assert!(lines.len() == 1);
-    let target_area = parse_input(&lines[0]);
-    let solution_count = count_solutions(&target_area);
-    println!("{}", solution_count);
+    shots
 }
 
-fn sum_to_n(n: u32) -> u32 {
-    n / 2 * (n + 1)
-}
+aoc_core::example_tests!(crate::YEAR, 17);
 
 #[test]
 fn example() {
-    let example = "target area: x=20..30, y=-10..-5";
-    let target_area = parse_input(example);
+    let target_area = parse(&read_example(17, 1).unwrap()).unwrap();
     let mut probe = Probe::new(7, 2);
     for _ in 0..6 {
         probe = step(probe);
@@ -144,23 +251,74 @@ fn example() {
     }
     probe = step(probe);
     assert!(contains(&probe, &target_area));
-    let velocity = high_shot(&target_area);
-    assert_eq!(velocity, (7, 9));
-    // let mut probe = Probe::new(6, 9);
-    // for _step in 0..100 {
-    //     probe = step(probe);
-    //     println!("{:?}", probe);
-    //     if contains(&probe, &target_area) {
-    //         println!("{:?} in {:?}", probe, target_area);
-    //         break;
-    //     }
-    // }
-    // let mut valid_x = Vec::new();
-    // for x in 0..target_area.x.1 {
-    //     let distance = sum_to_n(x as u32) as i32;
-    //     if target_area.x.0 <= distance && distance <= target_area.x.1 {
-    //         valid_x.push(x);
-    //     }
-    // }
-    // assert_eq!(valid_x, vec![6, 7]);
+
+    let shots = shots(&target_area).unwrap();
+    let velocities: Vec<_> = shots.iter().map(|shot| shot.velocity).collect();
+    for velocity in [(7, 2), (6, 3), (9, 0), (6, 9), (7, 9)] {
+        assert!(velocities.contains(&velocity), "{velocity:?}");
+    }
+    assert!(!velocities.contains(&(17, -4)));
+    let highest = shots.iter().find(|shot| shot.velocity == (6, 9)).unwrap();
+    assert_eq!((highest.apex, highest.first_step), (45, 20));
+}
+
+#[test]
+fn target_areas_level_with_the_launcher() {
+    // no triangular number is 4 or 5, so every shot leaves the area sideways
+    let target_area = parse("target area: x=4..5, y=-3..3").unwrap();
+    assert_eq!(
+        shots(&target_area).unwrap(),
+        simulated_shots(&target_area, 40)
+    );
+    // but a probe with no sideways speed can go up as high as it likes and fall back in
+    let target_area = parse("target area: x=-2..2, y=-3..3").unwrap();
+    assert!(shots(&target_area).is_err());
+}
+
+#[test]
+fn agrees_with_simulation() {
+    use aoc_core::differential::{check_agreement, Solver};
+    use proptest::prelude::*;
+    let above_or_below = prop_oneof![-30..0, 1..=30];
+    let target_areas = (
+        (-30..=30, -30..=30),
+        (above_or_below.clone(), above_or_below),
+    )
+        .prop_filter("the area is above or below the launcher", |(_, (a, b))| {
+            (*a > 0) == (*b > 0)
+        })
+        .prop_map(|((x1, x2), (y1, y2))| {
+            format!(
+                "target area: x={}..{}, y={}..{}",
+                min(x1, x2),
+                max(x1, x2),
+                min(y1, y2),
+                max(y1, y2)
+            )
+        });
+    check_agreement(
+        crate::YEAR,
+        17,
+        32,
+        target_areas,
+        &[
+            Solver::new("analytic", |input| {
+                let mut shots = shots(&parse(input)?)?;
+                shots.sort();
+                Ok::<_, Report>(format!("{shots:?}"))
+            }),
+            Solver::new("simulation", |input| {
+                let mut shots = simulated_shots(&parse(input)?, 60);
+                shots.sort();
+                Ok::<_, Report>(format!("{shots:?}"))
+            }),
+        ],
+    );
+}
+
+#[test]
+fn answers() {
+    let input = parse(&read_input(17).unwrap()).unwrap();
+    assert_eq!(part_one(&input).unwrap(), 7875);
+    assert_eq!(part_two(&input).unwrap(), 2321);
 }
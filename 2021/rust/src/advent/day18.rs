@@ -1,72 +1,72 @@
-use std::{iter::Peekable, ops::Add};
+use std::fmt::Display;
+use std::ops::Add;
 
 use crate::utils::*;
+use aoc_core::parse::{parse_lines, Cursor, ParseError};
+use eyre::{ContextCompat, Report};
 
-#[derive(Debug, Clone)]
-struct SnailNumber {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnailNumber {
     left: Box<Element>,
     right: Box<Element>,
 }
 
 impl SnailNumber {
-    // extra gross because the one is added to a regular number in the node
-    // the other is added to the next left or right digit, potentially in an entirely different part of the tree!
-    // have to backtrack with the number up
-    fn explode(&mut self, depth: u32) -> bool {
-        println!("depth {}, left: {:?}, right: {:?}", depth, self.left, self.right);
-        match *self.left {
-            Element::Pair(ref mut sn) => {
-                if depth >= 3 {
-                    println!("we should be exploding");
-                    *self.right = Element::Regular(match (*sn.right.clone(), *self.right.clone()) {
-                        (Element::Regular(ref a), Element::Regular(ref b)) => a + b,
-                        _ => panic!("oops"),
-                    });
-
-                    // *self.left = Element::Regular(0);
-                    println!("post-splode: {:?}", self);
-                    return true;
-                } else if sn.explode(depth + 1) {
-                    return true;
+    /// The two regular numbers in this pair, if that is all it holds.
+    fn regulars(&self) -> Option<(u32, u32)> {
+        match (&*self.left, &*self.right) {
+            (Element::Regular(left), Element::Regular(right)) => Some((*left, *right)),
+            _ => None,
+        }
+    }
+
+    /// Explodes the leftmost pair nested inside four others, given that this pair is nested
+    /// inside `depth`. The exploded pair's numbers are added to the first regular numbers either
+    /// side of it, but those can be anywhere in the tree, so whatever this pair cannot place is
+    /// handed back up as `(left, right)` for a parent to carry on.
+    fn explode(&mut self, depth: u32) -> Option<(u32, u32)> {
+        if let Element::Pair(pair) = &mut *self.left {
+            let carry = match pair.regulars() {
+                Some(exploded) if depth >= 3 => {
+                    *self.left = Element::Regular(0);
+                    Some(exploded)
                 }
+                _ => pair.explode(depth + 1),
+            };
+            if let Some((left, right)) = carry {
+                self.right.add_leftmost(right);
+                return Some((left, 0));
             }
-            _ => {}
         }
-        match *self.right.clone() {
-            Element::Pair(ref mut sn) => {
-                if depth >= 3 {
+        if let Element::Pair(pair) = &mut *self.right {
+            let carry = match pair.regulars() {
+                Some(exploded) if depth >= 3 => {
                     *self.right = Element::Regular(0);
-                    *self.left = Element::Regular(match (*sn.left.clone(), *self.left.clone()) {
-                        (Element::Regular(ref a), Element::Regular(ref b)) => a + b,
-                        _ => panic!("oops"),
-                    });
-                    return true;
-                } else if sn.explode(depth + 1) {
-                    return true;
+                    Some(exploded)
                 }
+                _ => pair.explode(depth + 1),
+            };
+            if let Some((left, right)) = carry {
+                self.left.add_rightmost(left);
+                return Some((0, right));
             }
-            _ => {}
         }
-        false
+        None
     }
 
+    /// Splits the leftmost regular number of 10 or more, returning whether there was one.
     fn split(&mut self) -> bool {
-        false
+        self.left.split() || self.right.split()
     }
 
     fn reduce(mut self) -> Self {
-        loop {
-            if self.explode(0) {
-                break;
-                continue;
-            } else if self.split() {
-                continue;
-            } else {
-                break;
-            }
-        }
+        while self.explode(0).is_some() || self.split() {}
         self
     }
+
+    pub fn magnitude(&self) -> u32 {
+        3 * self.left.magnitude() + 2 * self.right.magnitude()
+    }
 }
 
 impl Add<SnailNumber> for SnailNumber {
@@ -81,85 +81,185 @@ impl Add<SnailNumber> for SnailNumber {
     }
 }
 
-#[derive(Debug, Clone)]
-enum Element {
+impl Display for SnailNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{},{}]", self.left, self.right)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Element {
     Regular(u32),
     Pair(SnailNumber),
 }
 
-fn parse_element(characters: &mut impl Iterator<Item = char>) -> Element {
-    loop {
-        match characters.next().unwrap() {
-            '[' => {
-                return Element::Pair(SnailNumber {
-                    left: Box::new(parse_element(characters)),
-                    right: Box::new(parse_element(characters)),
+impl Element {
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Element::Regular(number) => *number += value,
+            Element::Pair(pair) => pair.left.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Element::Regular(number) => *number += value,
+            Element::Pair(pair) => pair.right.add_rightmost(value),
+        }
+    }
+
+    fn split(&mut self) -> bool {
+        match self {
+            Element::Regular(number) if *number >= 10 => {
+                *self = Element::Pair(SnailNumber {
+                    left: Box::new(Element::Regular(*number / 2)),
+                    right: Box::new(Element::Regular(number.div_ceil(2))),
                 });
+                true
             }
-            ']' => {}
-            ',' => {}
-            d => {
-                return Element::Regular(d.to_digit(10).unwrap());
-            }
+            Element::Regular(_) => false,
+            Element::Pair(pair) => pair.split(),
+        }
+    }
+
+    fn magnitude(&self) -> u32 {
+        match self {
+            Element::Regular(number) => *number,
+            Element::Pair(pair) => pair.magnitude(),
+        }
+    }
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Element::Regular(number) => write!(f, "{number}"),
+            Element::Pair(pair) => write!(f, "{pair}"),
         }
     }
 }
 
-fn parse_inner(characters: &mut impl Iterator<Item = char>) -> SnailNumber {
-    SnailNumber {
-        left: Box::new(parse_element(characters)),
-        right: Box::new(parse_element(characters)),
+fn parse_element(cursor: &mut Cursor) -> std::result::Result<Element, ParseError> {
+    if cursor.eat("[") {
+        let left = parse_element(cursor)?;
+        cursor.expect(",")?;
+        let right = parse_element(cursor)?;
+        cursor.expect("]")?;
+        Ok(Element::Pair(SnailNumber {
+            left: Box::new(left),
+            right: Box::new(right),
+        }))
+    } else {
+        Ok(Element::Regular(cursor.number()?))
     }
 }
 
-fn parse_line(line: String) -> SnailNumber {
-    parse_inner(&mut line.chars().peekable().skip(1))
+fn parse_line(line: &str) -> std::result::Result<SnailNumber, ParseError> {
+    let mut cursor = Cursor::new(line);
+    let number = match parse_element(&mut cursor)? {
+        Element::Pair(number) => number,
+        Element::Regular(_) => return Err(ParseError::at(line, 0, "expected a pair")),
+    };
+    cursor.end()?;
+    Ok(number)
+}
+
+type Homework = Vec<SnailNumber>;
+
+pub fn parse(input: &str) -> Result<Homework> {
+    Ok(parse_lines(input, parse_line)?)
+}
+
+/// The sum of every number in the homework, in order.
+fn sum(homework: &[SnailNumber]) -> Result<SnailNumber> {
+    homework
+        .iter()
+        .cloned()
+        .reduce(Add::add)
+        .wrap_err("the homework is empty")
 }
 
-pub fn part_one() {
-    let ip = problem_input_path(18, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    // assert!(lines.len() == 1);
-    // let target_area = parse_input(&lines[0]);
-    // let (x, y) = high_shot(&target_area);
-    // let mut max_y = 0;
-    // let mut probe = Probe::new(x, y);
-    // while !contains(&probe, &target_area) {
-    //     probe = step(probe);
-    //     if probe.position.1 > max_y {
-    //         max_y = probe.position.1;
-    //     }
-    //     if probe.position.1 < target_area.y.0 {
-    //         panic!("oops")
-    //     }
-    // }
-    // println!("{}", max_y);
+pub fn part_one(homework: &Homework) -> Result<u32> {
+    Ok(sum(homework)?.magnitude())
 }
 
-pub fn part_two() {
-    let ip = problem_input_path(18, Some(1));
-    let lines: Vec<String> = read_file_split_on(&ip, "\n").unwrap();
-    // assert!(lines.len() == 1);
-    // let target_area = parse_input(&lines[0]);
-    // let solution_count = count_solutions(&target_area);
-    // println!("{}", solution_count);
+/// The largest magnitude from adding any two different numbers, either way round since
+/// snailfish addition does not commute.
+pub fn part_two(homework: &Homework) -> Result<u32> {
+    let sums = homework.iter().enumerate().flat_map(|(i, a)| {
+        homework
+            .iter()
+            .enumerate()
+            .filter(move |&(j, _)| i != j)
+            .map(move |(_, b)| (a.clone() + b.clone()).magnitude())
+    });
+    sums.max()
+        .ok_or_else(|| Report::msg("the homework needs at least two numbers"))
 }
 
+aoc_core::example_tests!(crate::YEAR, 18);
+
 #[test]
 fn example() {
-    let snail = parse_line("[[[[[9,8],1],2],3],4]".to_string());
-    println!("{:?}", snail.reduce());
-    let example = r#"[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
-[[[5,[2,8]],4],[5,[[9,9],0]]]
-[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
-[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
-[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
-[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
-[[[[5,4],[7,7]],8],[[8,3],8]]
-[[9,3],[[9,9],[6,[4,9]]]]
-[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
-[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"#;
-    let lines: Vec<String> = example.split("\n").map(|s| s.to_string()).collect();
-    println!("{:?}", parse_line(lines[0].clone()));
+    let explode_once = |line: &str| {
+        let mut number = parse_line(line).unwrap();
+        number.explode(0);
+        number.to_string()
+    };
+    assert_eq!(explode_once("[[[[[9,8],1],2],3],4]"), "[[[[0,9],2],3],4]");
+    assert_eq!(explode_once("[7,[6,[5,[4,[3,2]]]]]"), "[7,[6,[5,[7,0]]]]");
+    assert_eq!(explode_once("[[6,[5,[4,[3,2]]]],1]"), "[[6,[5,[7,0]]],3]");
+    assert_eq!(
+        explode_once("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"),
+        "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
+    );
+    assert_eq!(
+        explode_once("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"),
+        "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"
+    );
+
+    let sum_of = |lines: &[&str]| sum(&parse(&lines.join("\n")).unwrap()).unwrap().to_string();
+    assert_eq!(
+        sum_of(&["[[[[4,3],4],4],[7,[[8,4],9]]]", "[1,1]"]),
+        "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+    );
+    assert_eq!(
+        sum_of(&["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]),
+        "[[[[5,0],[7,4]],[5,5]],[6,6]]"
+    );
+    let homework = read_example(18, 2).unwrap();
+    assert_eq!(
+        sum_of(&homework.lines().collect::<Vec<_>>()),
+        "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+    );
+    let homework = read_example(18, 1).unwrap();
+    assert_eq!(
+        sum_of(&homework.lines().collect::<Vec<_>>()),
+        "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+    );
 
+    let magnitude = |line: &str| parse_line(line).unwrap().magnitude();
+    assert_eq!(magnitude("[[1,2],[[3,4],5]]"), 143);
+    assert_eq!(magnitude("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"), 1384);
+    assert_eq!(magnitude("[[[[1,1],[2,2]],[3,3]],[4,4]]"), 445);
+    assert_eq!(magnitude("[[[[3,0],[5,3]],[4,4]],[5,5]]"), 791);
+    assert_eq!(
+        magnitude("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"),
+        3488
+    );
+}
+
+#[test]
+fn displays_as_parsed() {
+    let input = read_input(18).unwrap();
+    for (line, number) in input.lines().zip(parse(&input).unwrap()) {
+        assert_eq!(number.to_string(), line.trim());
+    }
+}
+
+#[test]
+fn answers() {
+    let homework = parse(&read_input(18).unwrap()).unwrap();
+    assert_eq!(part_one(&homework).unwrap(), 4017);
+    assert_eq!(part_two(&homework).unwrap(), 4583);
 }
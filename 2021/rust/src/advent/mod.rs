@@ -15,4 +15,4 @@ pub mod day14;
 pub mod day15;
 pub mod day16;
 pub mod day17;
-pub mod day18;
\ No newline at end of file
+pub mod day18;
@@ -1,28 +1,48 @@
-use crate::utils::*;
+use aoc_core::parse::parse_lines;
+use aoc_core::scan;
 use eyre::{Report, Result, WrapErr};
-use std::{fs, str::FromStr};
+use std::str::FromStr;
 
 // TODO: impl From<(Shape, Shape)> for Outcome instead of using PartialOrd which should have been Ord anyway
 
+/// The second column of the strategy guide, which means a [Shape] in part one
+/// and an [Outcome] in part two.
 #[derive(Clone, Copy, Debug)]
-enum Outcome {
-    Lose,
-    Draw,
-    Win,
+pub enum Column {
+    X,
+    Y,
+    Z,
 }
 
-impl FromStr for Outcome {
+impl FromStr for Column {
     type Err = Report;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.trim() {
-            "X" => Ok(Self::Lose),
-            "Y" => Ok(Self::Draw),
-            "Z" => Ok(Self::Win),
+            "X" => Ok(Self::X),
+            "Y" => Ok(Self::Y),
+            "Z" => Ok(Self::Z),
             other => Err(Report::msg(other.to_string())),
         }
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl From<Column> for Outcome {
+    fn from(value: Column) -> Self {
+        match value {
+            Column::X => Self::Lose,
+            Column::Y => Self::Draw,
+            Column::Z => Self::Win,
+        }
+    }
+}
+
 impl From<Outcome> for u32 {
     fn from(value: Outcome) -> Self {
         match value {
@@ -34,7 +54,7 @@ impl From<Outcome> for u32 {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
@@ -44,14 +64,24 @@ impl FromStr for Shape {
     type Err = Report;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.trim() {
-            "A" | "X" => Ok(Self::Rock),
-            "B" | "Y" => Ok(Self::Paper),
-            "C" | "Z" => Ok(Self::Scissors),
+            "A" => Ok(Self::Rock),
+            "B" => Ok(Self::Paper),
+            "C" => Ok(Self::Scissors),
             other => Err(Report::msg(other.to_string())),
         }
     }
 }
 
+impl From<Column> for Shape {
+    fn from(value: Column) -> Self {
+        match value {
+            Column::X => Self::Rock,
+            Column::Y => Self::Paper,
+            Column::Z => Self::Scissors,
+        }
+    }
+}
+
 impl From<Shape> for u32 {
     fn from(value: Shape) -> Self {
         match value {
@@ -76,35 +106,17 @@ impl PartialOrd for Shape {
     }
 }
 
-type Round = (Shape, Shape);
+type Round = (Shape, Column);
 type Strategy = Vec<Round>;
 
-pub fn part_one() -> Result<u32> {
-    let input_path = problem_input_path(2, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let strategy = parse_strategy_one(&content)?;
-    let result = part_one_inner(strategy);
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<u32> {
-    let input_path = problem_input_path(2, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
-
-fn parse_strategy_one(input: &str) -> Result<Strategy> {
-    let mut strategy = Vec::new();
-    for [opponent, mine] in input.split_whitespace().array_chunks() {
-        strategy.push((
-            str::parse(opponent).wrap_err_with(|| format!("failed to parse [{opponent}]"))?,
-            str::parse(mine).wrap_err_with(|| format!("failed to parse [{mine}]"))?,
-        ));
-    }
-    Ok(strategy)
+pub fn parse(input: &str) -> Result<Strategy> {
+    Ok(parse_lines(input, |line| {
+        let (opponent, mine) = scan!(line.trim(), "{opponent} {mine}" => String, String)?;
+        Ok::<_, Report>((
+            str::parse(&opponent).wrap_err_with(|| format!("unknown shape [{opponent}]"))?,
+            str::parse(&mine).wrap_err_with(|| format!("unknown column [{mine}]"))?,
+        ))
+    })?)
 }
 
 fn resolve_round(opponent: Shape, mine: Shape) -> u32 {
@@ -117,11 +129,11 @@ fn resolve_round(opponent: Shape, mine: Shape) -> u32 {
     move_score + result_score
 }
 
-fn part_one_inner(strategy: Strategy) -> u32 {
-    strategy
-        .into_iter()
-        .map(|(opponent, mine)| resolve_round(opponent, mine))
-        .sum()
+pub fn part_one(strategy: &Strategy) -> Result<u32> {
+    Ok(strategy
+        .iter()
+        .map(|&(opponent, mine)| resolve_round(opponent, mine.into()))
+        .sum())
 }
 
 fn shape_from_outcome(opponent: Shape, outcome: Outcome) -> Shape {
@@ -136,13 +148,10 @@ fn shape_from_outcome(opponent: Shape, outcome: Outcome) -> Shape {
     }
 }
 
-fn part_two_inner(input: &str) -> Result<u32> {
+pub fn part_two(strategy: &Strategy) -> Result<u32> {
     let mut score = 0;
-    for [opponent, mine] in input.split_whitespace().array_chunks() {
-        let opponent_shape: Shape =
-            str::parse(opponent).wrap_err_with(|| format!("failed to parse [{opponent}]"))?;
-        let desired_outcome: Outcome =
-            str::parse(mine).wrap_err_with(|| format!("failed to parse [{mine}]"))?;
+    for &(opponent_shape, mine) in strategy {
+        let desired_outcome: Outcome = mine.into();
         let desired_shape = shape_from_outcome(opponent_shape, desired_outcome);
         let outcome_score: u32 = desired_outcome.into();
         let shape_score: u32 = desired_shape.into();
@@ -154,22 +163,19 @@ fn part_two_inner(input: &str) -> Result<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-A Y
-B X
-C Z"#;
+    aoc_core::example_tests!(crate::YEAR, 2);
 
     #[test]
     fn part_one_works() {
-        let strategy = parse_strategy_one(TEST_INPUT).unwrap();
-        assert_eq!(part_one_inner(strategy), 15);
-        assert_eq!(part_one().unwrap(), 10941);
+        let strategy = parse(&read_input(2).unwrap()).unwrap();
+        assert_eq!(part_one(&strategy).unwrap(), 10941);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 12);
-        assert_eq!(part_two().unwrap(), 13071);
+        let strategy = parse(&read_input(2).unwrap()).unwrap();
+        assert_eq!(part_two(&strategy).unwrap(), 13071);
     }
 }
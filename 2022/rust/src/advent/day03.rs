@@ -1,17 +1,18 @@
 use crate::utils::*;
-use eyre::Result;
-use std::{collections::BTreeSet, fs};
+use aoc_core::parse::{parse_lines, ParseError};
+use eyre::{ContextCompat, Result};
+use std::collections::BTreeSet;
 
-struct Rucksack {
+pub struct Rucksack {
     left: Compartment,
     right: Compartment,
 }
 
 impl Rucksack {
-    fn shared_item(&self) -> char {
+    fn shared_item(&self) -> Option<char> {
         let left_set: BTreeSet<char> = self.left.items.keys().copied().collect();
         let right_set: BTreeSet<char> = self.right.items.keys().copied().collect();
-        *left_set.intersection(&right_set).next().unwrap()
+        left_set.intersection(&right_set).next().copied()
     }
 
     fn item_set(&self) -> BTreeSet<char> {
@@ -22,79 +23,69 @@ impl Rucksack {
 }
 
 trait Priority {
-    fn priority(self) -> u32;
+    fn priority(self) -> Option<u32>;
 }
 
 impl Priority for char {
-    fn priority(self) -> u32 {
-        if self.is_alphabetic() {
-            if self.is_uppercase() {
-                (self as u32) - 38
-            } else {
-                (self as u32) - 96
-            }
-        } else {
-            panic!("expected only alphabetic characters! found {self}")
+    /// Only letters have priorities, which parsing has already checked every item is.
+    fn priority(self) -> Option<u32> {
+        match self {
+            'a'..='z' => Some(self as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Some(self as u32 - 'A' as u32 + 27),
+            _ => None,
         }
     }
 }
 
-pub fn part_one() -> Result<u32> {
-    let input_path = problem_input_path(3, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let rucksacks = parse_rucksacks(&content);
-    let result = part_one_inner(rucksacks);
-    println!("{result}");
-    Ok(result)
-}
+type Rucksacks = Vec<Rucksack>;
 
-pub fn part_two() -> Result<u32> {
-    let input_path = problem_input_path(3, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let rucksacks = parse_rucksacks(&content);
-    let result = part_two_inner(rucksacks);
-    println!("{result}");
-    Ok(result)
+fn parse_rucksack(line: &str) -> Result<Rucksack, ParseError> {
+    if let Some(column) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+        return Err(ParseError::at(line, column, "items should be letters"));
+    }
+    if !line.len().is_multiple_of(2) {
+        return Err(ParseError::at(
+            line,
+            line.len(),
+            "a rucksack should split into two equal compartments",
+        ));
+    }
+    let (left, right) = line.split_at(line.len() / 2);
+    Ok(Rucksack {
+        left: left.into(),
+        right: right.into(),
+    })
 }
 
-fn parse_rucksacks(input: &str) -> Vec<Rucksack> {
-    input
-        .lines()
-        .map(|line| {
-            let line = line.trim();
-            let length = line.len();
-            let (left, right) = line.split_at(length / 2);
-            let left_compartment: Compartment = left.into();
-            let right_compartment: Compartment = right.into();
-            Rucksack {
-                left: left_compartment,
-                right: right_compartment,
-            }
-        })
-        .collect()
+pub fn parse(input: &str) -> Result<Rucksacks> {
+    Ok(parse_lines(input, |line| parse_rucksack(line.trim()))?)
 }
 
-fn part_one_inner(rucksacks: Vec<Rucksack>) -> u32 {
+pub fn part_one(rucksacks: &Rucksacks) -> Result<u32> {
     rucksacks
         .iter()
-        .map(Rucksack::shared_item)
-        .map(Priority::priority)
+        .enumerate()
+        .map(|(index, rucksack)| {
+            rucksack
+                .shared_item()
+                .and_then(Priority::priority)
+                .wrap_err_with(|| format!("rucksack {} has no item in both halves", index + 1))
+        })
         .sum()
 }
 
-fn part_two_inner(rucksacks: Vec<Rucksack>) -> u32 {
+pub fn part_two(rucksacks: &Rucksacks) -> Result<u32> {
     rucksacks
         .chunks(3)
-        .map(|group| {
+        .enumerate()
+        .map(|(index, group)| {
             group
                 .iter()
                 .map(Rucksack::item_set)
                 .reduce(|a, b| a.intersection(&b).copied().collect::<BTreeSet<char>>())
-                .unwrap()
-                .into_iter()
-                .next()
-                .unwrap()
-                .priority()
+                .and_then(|badges| badges.into_iter().next())
+                .and_then(Priority::priority)
+                .wrap_err_with(|| format!("group {} has no badge in common", index + 1))
         })
         .sum()
 }
@@ -103,24 +94,17 @@ fn part_two_inner(rucksacks: Vec<Rucksack>) -> u32 {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw"#;
+    aoc_core::example_tests!(crate::YEAR, 3);
 
     #[test]
     fn part_one_works() {
-        let rucksacks = parse_rucksacks(TEST_INPUT);
-        assert_eq!(part_one_inner(rucksacks), 157);
-        assert_eq!(part_one().unwrap(), 8233);
+        let rucksacks = parse(&read_input(3).unwrap()).unwrap();
+        assert_eq!(part_one(&rucksacks).unwrap(), 8233);
     }
 
     #[test]
     fn part_two_works() {
-        let rucksacks = parse_rucksacks(TEST_INPUT);
-        assert_eq!(part_two_inner(rucksacks), 70);
-        assert_eq!(part_two().unwrap(), 2821);
+        let rucksacks = parse(&read_input(3).unwrap()).unwrap();
+        assert_eq!(part_two(&rucksacks).unwrap(), 2821);
     }
 }
@@ -1,8 +1,8 @@
-use crate::utils::*;
+use aoc_core::parse::parse_lines;
 use eyre::{Report, Result};
-use std::{fs, str::FromStr};
+use std::str::FromStr;
 
-struct Assignment {
+pub struct Assignment {
     start: u32,
     end: u32,
 }
@@ -30,7 +30,7 @@ impl Assignment {
     }
 }
 
-struct Pair {
+pub struct Pair {
     left: Assignment,
     right: Assignment,
 }
@@ -48,75 +48,45 @@ impl FromStr for Pair {
 }
 
 impl Pair {
-    fn contains(self) -> bool {
+    fn contains(&self) -> bool {
         self.left.contains(&self.right) || self.right.contains(&self.left)
     }
 
-    fn overlap(self) -> bool {
+    fn overlap(&self) -> bool {
         self.left.overlap(&self.right)
     }
 }
 
-pub fn part_one() -> Result<u32> {
-    let input_path = problem_input_path(4, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let assignments = parse_assignments(&content)?;
-    let result = part_one_inner(assignments);
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<u32> {
-    let input_path = problem_input_path(4, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let assignments = parse_assignments(&content)?;
-    let result = part_two_inner(assignments);
-    println!("{result}");
-    Ok(result)
-}
+type Assignments = Vec<Pair>;
 
-fn parse_assignments(input: &str) -> Result<Vec<Pair>> {
-    input.trim().lines().map(str::parse).collect()
+pub fn parse(input: &str) -> Result<Assignments> {
+    Ok(parse_lines(input, str::parse)?)
 }
 
-fn part_one_inner(assignments: Vec<Pair>) -> u32 {
-    assignments
-        .into_iter()
-        .map(Pair::contains)
-        .filter(|b| *b)
-        .count() as u32
+pub fn part_one(assignments: &Assignments) -> Result<u32> {
+    Ok(assignments.iter().filter(|pair| pair.contains()).count() as u32)
 }
 
-fn part_two_inner(assignments: Vec<Pair>) -> u32 {
-    assignments
-        .into_iter()
-        .map(Pair::overlap)
-        .filter(|b| *b)
-        .count() as u32
+pub fn part_two(assignments: &Assignments) -> Result<u32> {
+    Ok(assignments.iter().filter(|pair| pair.overlap()).count() as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8"#;
+    aoc_core::example_tests!(crate::YEAR, 4);
 
     #[test]
     fn part_one_works() {
-        let assignments = parse_assignments(TEST_INPUT).unwrap();
-        assert_eq!(part_one_inner(assignments), 2);
-        assert_eq!(part_one().unwrap(), 498);
+        let assignments = parse(&read_input(4).unwrap()).unwrap();
+        assert_eq!(part_one(&assignments).unwrap(), 498);
     }
 
     #[test]
     fn part_two_works() {
-        let assignments = parse_assignments(TEST_INPUT).unwrap();
-        assert_eq!(part_two_inner(assignments), 4);
-        assert_eq!(part_two().unwrap(), 859);
+        let assignments = parse(&read_input(4).unwrap()).unwrap();
+        assert_eq!(part_two(&assignments).unwrap(), 859);
     }
 }
@@ -1,26 +1,38 @@
-use crate::utils::*;
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::scan;
 use eyre::{Context, ContextCompat, Report, Result};
 use itertools::Itertools;
-use std::{fs, str::FromStr};
+use std::str::FromStr;
 
-#[derive(Debug)]
-struct Stacks {
+#[derive(Debug, Clone)]
+pub struct Stacks {
     stacks: Vec<Vec<char>>,
 }
 
 impl FromStr for Stacks {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines().rev();
-        let stack_count = lines.next().unwrap().split_whitespace().count();
-        let mut stacks = Vec::with_capacity(stack_count);
-        for _ in 0..stack_count {
-            stacks.push(Vec::new());
-        }
-        for line in lines {
+        let lines: Vec<&str> = s.lines().collect();
+        let mut lines = lines.into_iter().enumerate().rev();
+        let (_, numbers) = lines
+            .next()
+            .ok_or_else(|| ParseError::at(s, 0, "expected a row of stack numbers"))?;
+        let stack_count = numbers.split_whitespace().count();
+        let mut stacks = vec![Vec::new(); stack_count];
+        for (index, line) in lines {
             for (i, mut chars) in line.chars().chunks(4).into_iter().enumerate() {
                 if let Some(c) = chars.find(|c| c.is_alphabetic()) {
-                    stacks[i].push(c)
+                    stacks
+                        .get_mut(i)
+                        .ok_or_else(|| {
+                            ParseError::at(
+                                line,
+                                i * 4,
+                                format!("there are only {stack_count} stacks"),
+                            )
+                            .on_line(index + 1)
+                        })?
+                        .push(c)
                 }
             }
         }
@@ -32,10 +44,12 @@ impl Stacks {
     fn apply_command(&mut self, command: &Command) -> Result<()> {
         let [from, to] = self
             .stacks
-            .get_many_mut([command.from, command.to])
+            .get_disjoint_mut([command.from, command.to])
             .wrap_err_with(|| Report::msg("failed to get the from/to vectors".to_string()))?;
         for _ in 0..command.amt {
-            let tmp = from.pop().wrap_err_with(|| Report::msg(String::new()))?;
+            let tmp = from
+                .pop()
+                .wrap_err_with(|| format!("stack {} ran out of crates", command.from + 1))?;
             to.push(tmp);
         }
         Ok(())
@@ -44,33 +58,48 @@ impl Stacks {
     fn apply_command_multiple(&mut self, command: &Command) -> Result<()> {
         let [from, to] = self
             .stacks
-            .get_many_mut([command.from, command.to])
+            .get_disjoint_mut([command.from, command.to])
             .wrap_err_with(|| Report::msg("failed to get the from/to vectors".to_string()))?;
-        let moving = from.split_off(from.len() - command.amt);
+        let remaining = from.len().checked_sub(command.amt).wrap_err_with(|| {
+            format!(
+                "stack {} has fewer than {} crates",
+                command.from + 1,
+                command.amt
+            )
+        })?;
+        let moving = from.split_off(remaining);
         to.extend(moving);
         Ok(())
     }
+
+    /// The crate on top of each stack.
+    fn tops(&self) -> Result<String> {
+        self.stacks
+            .iter()
+            .enumerate()
+            .map(|(index, stack)| {
+                stack
+                    .last()
+                    .wrap_err_with(|| format!("stack {} is empty", index + 1))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug)]
-struct Command {
+pub struct Command {
     from: usize,
     to: usize,
     amt: usize,
 }
 
 impl FromStr for Command {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let command_parts: Vec<usize> = s
-            .split_whitespace()
-            .map(str::parse::<usize>)
-            .filter(|v| v.is_ok())
-            .map(Result::unwrap)
-            .collect();
-        let [amt, from, to]: [usize; 3] = command_parts
-            .try_into()
-            .map_err(|o| Report::msg(format!("incorrect number of parts [{o:?}]")))?;
+        let (amt, from, to) = scan!(s, "move {amount} from {from} to {to}" => usize, usize, usize)?;
+        if from == 0 || to == 0 {
+            return Err(ParseError::at(s, 0, "stacks are numbered from 1"));
+        }
         Ok(Command {
             from: from - 1,
             to: to - 1,
@@ -80,94 +109,77 @@ impl FromStr for Command {
 }
 
 #[derive(Debug)]
-struct Commands {
+pub struct Commands {
     commands: Vec<Command>,
 }
 
 impl FromStr for Commands {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let commands: Result<Vec<Command>, _> = s.lines().map(str::parse).collect();
         Ok(Commands {
-            commands: commands?,
+            commands: parse_lines(s, str::parse)?,
         })
     }
 }
 
-pub fn part_one() -> Result<String> {
-    let input_path = problem_input_path(5, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let (stacks, commands) = parse_input(&content)?;
-    let result = part_one_inner(stacks, commands)?;
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<String> {
-    let input_path = problem_input_path(5, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let (stacks, commands) = parse_input(&content)?;
-    let result = part_two_inner(stacks, commands)?;
-    println!("{result}");
-    Ok(result)
-}
+type Procedure = (Stacks, Commands);
 
-fn part_one_inner(mut stacks: Stacks, commands: Commands) -> Result<String> {
-    for command in commands.commands {
-        stacks.apply_command(&command)?;
+pub fn part_one((stacks, commands): &Procedure) -> Result<String> {
+    let mut stacks = stacks.clone();
+    for command in commands.commands.iter() {
+        stacks.apply_command(command)?;
     }
-    Ok(stacks
-        .stacks
-        .into_iter()
-        .map(|mut v| v.pop().unwrap())
-        .collect())
+    stacks.tops()
 }
 
-fn part_two_inner(mut stacks: Stacks, commands: Commands) -> Result<String> {
-    for command in commands.commands {
-        stacks.apply_command_multiple(&command)?;
+pub fn part_two((stacks, commands): &Procedure) -> Result<String> {
+    let mut stacks = stacks.clone();
+    for command in commands.commands.iter() {
+        stacks.apply_command_multiple(command)?;
     }
-    Ok(stacks
-        .stacks
-        .into_iter()
-        .map(|mut v| v.pop().unwrap())
-        .collect())
+    stacks.tops()
 }
 
-fn parse_input(input: &str) -> Result<(Stacks, Commands)> {
+pub fn parse(input: &str) -> Result<Procedure> {
     let (stack_input, command_input) = input.split_once("\n\n").wrap_err_with(|| {
         Report::msg("input didn't split by double newline correctly".to_string())
     })?;
     let stacks = str::parse(stack_input)?;
-    let commands = str::parse(command_input)?;
+    // the commands start after the stacks and the blank line below them
+    let first_command = stack_input.lines().count() + 2;
+    let commands =
+        str::parse(command_input).map_err(|error: ParseError| error.on_line(first_command))?;
     Ok((stacks, commands))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
+    aoc_core::example_tests!(crate::YEAR, 5);
 
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2"#;
+    #[test]
+    fn reports_impossible_moves() {
+        let procedure = parse("[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+        let error = part_one(&procedure).unwrap_err();
+        assert_eq!(error.to_string(), "stack 1 is empty");
+        let procedure = parse("[A]    \n 1   2 \n\nmove 2 from 1 to 2\n").unwrap();
+        assert!(part_one(&procedure).is_err());
+        assert!(part_two(&procedure).is_err());
+        let error = parse("[A]    \n 1   2 \n\nmove 1 from 0 to 2\n").unwrap_err();
+        assert_eq!(error.downcast::<ParseError>().unwrap().line, 4);
+    }
 
     #[test]
     fn part_one_works() {
-        let (stacks, commands) = parse_input(TEST_INPUT).unwrap();
-        assert_eq!(part_one_inner(stacks, commands).unwrap(), "CMZ");
-        assert_eq!(part_one().unwrap(), "DHBJQJCCW");
+        let procedure = parse(&read_input(5).unwrap()).unwrap();
+        assert_eq!(part_one(&procedure).unwrap(), "DHBJQJCCW");
     }
 
     #[test]
     fn part_two_works() {
-        let (stacks, commands) = parse_input(TEST_INPUT).unwrap();
-        assert_eq!(part_two_inner(stacks, commands).unwrap(), "MCD");
-        assert_eq!(part_two().unwrap(), "WJVRLSJJT");
+        let procedure = parse(&read_input(5).unwrap()).unwrap();
+        assert_eq!(part_two(&procedure).unwrap(), "WJVRLSJJT");
     }
 }
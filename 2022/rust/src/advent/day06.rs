@@ -1,70 +1,52 @@
-use crate::utils::*;
-use eyre::Result;
+use eyre::{ContextCompat, Report, Result};
 use itertools::Itertools;
-use std::fs;
 
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(6, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = solve::<4>(&content);
-    println!("{result}");
-    Ok(result)
+type Datastream = String;
+
+pub fn parse(input: &str) -> Result<Datastream> {
+    let input = input.trim();
+    // this solution requires the input to all be ascii so we can just use the byte format
+    // if it wasn't ascii, we could use v: Vec<char> = input.chars().collect(); v.windows()
+    if !input.is_ascii() {
+        return Err(Report::msg("expected an ascii datastream"));
+    }
+    Ok(input.to_owned())
 }
 
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(6, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = solve::<14>(&content);
-    println!("{result}");
-    Ok(result)
+pub fn part_one(datastream: &Datastream) -> Result<usize> {
+    solve::<4>(datastream)
 }
 
-fn solve<const N: usize>(input: &str) -> usize {
-    assert!(input.is_ascii());
-    // this solution requires the input to all be ascii so we can just use the byte format
-    // if it wasn't ascii, we could use v: Vec<char> = input.chars().collect(); v.windows()
-    input
+pub fn part_two(datastream: &Datastream) -> Result<usize> {
+    solve::<14>(datastream)
+}
+
+fn solve<const N: usize>(input: &str) -> Result<usize> {
+    let (position, _) = input
         .as_bytes()
         .windows(N)
-        .enumerate().find(|(_, w)| w.iter().all_unique())
-        .unwrap()
-        .0
-        + N
+        .enumerate()
+        .find(|(_, w)| w.iter().all_unique())
+        .wrap_err_with(|| format!("no marker of length {N} found"))?;
+    Ok(position + N)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUTS_PART_ONE: [(&str, usize); 5] = [
-        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7),
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 6),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
-    ];
+    aoc_core::example_tests!(crate::YEAR, 6);
 
     #[test]
     fn part_one_works() {
-        for (input, expected) in TEST_INPUTS_PART_ONE {
-            assert_eq!(solve::<4>(input), expected);
-        }
-        assert_eq!(part_one().unwrap(), 1275);
+        let datastream = parse(&read_input(6).unwrap()).unwrap();
+        assert_eq!(part_one(&datastream).unwrap(), 1275);
     }
 
-    const TEST_INPUTS_PART_TWO: [(&str, usize); 5] = [
-        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19),
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 23),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
-    ];
-
     #[test]
     fn part_two_works() {
-        for (input, expected) in TEST_INPUTS_PART_TWO {
-            assert_eq!(solve::<14>(input), expected);
-        }
-        assert_eq!(part_two().unwrap(), 3605);
+        let datastream = parse(&read_input(6).unwrap()).unwrap();
+        assert_eq!(part_two(&datastream).unwrap(), 3605);
     }
 }
@@ -1,8 +1,7 @@
-use crate::utils::*;
+use aoc_core::parse::{LineError, ParseError};
 use eyre::{ContextCompat, Report, Result};
 use std::{
-    fs,
-    iter::{once, Peekable},
+    iter::{once, Enumerate, Peekable},
     str::{FromStr, Lines},
 };
 
@@ -11,7 +10,7 @@ trait FileSystemSize {
 }
 
 #[derive(Debug)]
-struct Directory {
+pub struct Directory {
     _name: String,
     contents: Vec<FileSystemEntry>,
 }
@@ -24,7 +23,7 @@ impl Directory {
         }
     }
 
-    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &FileSystemEntry> + 'a> {
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a FileSystemEntry> + 'a> {
         Box::new(
             self.contents
                 .iter()
@@ -90,7 +89,7 @@ impl FromStr for CDLine {
 }
 
 enum LSLine {
-    Dir(String),
+    Dir { _name: String },
     File(usize, String),
 }
 
@@ -102,11 +101,12 @@ impl FromStr for LSLine {
     type Err = Report;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with(LSLine::DIR_PREFIX) {
-            Ok(LSLine::Dir(
-                s.strip_prefix(LSLine::DIR_PREFIX)
+            Ok(LSLine::Dir {
+                _name: s
+                    .strip_prefix(LSLine::DIR_PREFIX)
                     .expect("ensured with starts_with")
                     .into(),
-            ))
+            })
         } else {
             let (size, name) = s.split_once(' ').wrap_err_with(|| {
                 Report::msg(format!(
@@ -118,11 +118,16 @@ impl FromStr for LSLine {
     }
 }
 
-fn parse_inner(
-    mut d: Directory,
-    mut input: Peekable<Lines<'_>>,
-) -> Result<(Directory, Peekable<Lines<'_>>)> {
-    while let Some(line) = input.peek() {
+/// The terminal output, with each line's index.
+type Terminal<'a> = Peekable<Enumerate<Lines<'a>>>;
+
+/// Parses one line of terminal output, pointing any failure at where it is in the output.
+fn parse_line<T: FromStr<Err = Report>>((index, line): (usize, &str)) -> Result<T, ParseError> {
+    str::parse(line).map_err(|error: Report| error.locate(line).on_line(index + 1))
+}
+
+fn parse_inner(mut d: Directory, mut input: Terminal<'_>) -> Result<(Directory, Terminal<'_>)> {
+    while let Some((_, line)) = input.peek() {
         if line.starts_with(CDLine::PREFIX) {
             if line.ends_with("..") {
                 // manually consume since we don't need a whole function for it.
@@ -142,56 +147,41 @@ fn parse_inner(
     Ok((d, input))
 }
 
-fn parse_ls(
-    mut d: Directory,
-    mut input: Peekable<Lines<'_>>,
-) -> Result<(Directory, Peekable<Lines<'_>>)> {
-    input
+fn parse_ls(mut d: Directory, mut input: Terminal<'_>) -> Result<(Directory, Terminal<'_>)> {
+    let (index, command) = input
         .next()
-        .expect("callers guarantee the first line is $ ls");
-    while let Some(line) = input.next_if(|line| !line.starts_with('$')) {
-        let ls_line: LSLine = str::parse(line)?;
+        .expect("callers guarantee there is a line to parse");
+    if command != "$ ls" {
+        let error = ParseError::at(command, 0, "expected $ ls or $ cd").on_line(index + 1);
+        return Err(error.into());
+    }
+    while let Some(line) = input.next_if(|(_, line)| !line.starts_with('$')) {
+        let ls_line: LSLine = parse_line(line)?;
         match ls_line {
             LSLine::File(size, name) => d
                 .contents
                 .push(FileSystemEntry::File(File { _name: name, size })),
             // We ignore dirs because we add them after we cd into them
             // we assume the order of the vec for the directory contents doesn't matter. if it does we can alphabetize it later.
-            LSLine::Dir(_) => {}
+            LSLine::Dir { .. } => {}
         }
     }
     Ok((d, input))
 }
 
-fn parse_cd(mut input: Peekable<Lines<'_>>) -> Result<(Directory, Peekable<Lines<'_>>)> {
-    let cd_line: CDLine = str::parse(
-        input
-            .next()
-            .expect("callers guarantee the first line is `$ cd foo`"),
-    )?;
+fn parse_cd(mut input: Terminal<'_>) -> Result<(Directory, Terminal<'_>)> {
+    let cd_line: CDLine = parse_line(input.next().wrap_err("expected a $ cd")?)?;
     let d = Directory::named(cd_line.0);
     let (d, input) = parse_inner(d, input)?;
     Ok((d, input))
 }
 
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(7, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(7, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<Directory> {
+    let (root, _) = parse_cd(input.lines().enumerate().peekable())?;
+    Ok(root)
 }
 
-fn part_one_inner(input: &str) -> Result<usize> {
-    let (root, _) = parse_cd(input.lines().peekable())?;
+pub fn part_one(root: &Directory) -> Result<usize> {
     Ok(root
         .iter()
         .filter(|fse| match fse {
@@ -202,10 +192,9 @@ fn part_one_inner(input: &str) -> Result<usize> {
         .sum())
 }
 
-fn part_two_inner(input: &str) -> Result<usize> {
+pub fn part_two(root: &Directory) -> Result<usize> {
     const TOTAL_SPACE: usize = 70000000;
     const REQUIRED_SPACE: usize = 30000000;
-    let (root, _) = parse_cd(input.lines().peekable())?;
     let used_space = TOTAL_SPACE - root.size();
     let minimum_size = REQUIRED_SPACE - used_space;
     root.iter()
@@ -221,40 +210,19 @@ fn part_two_inner(input: &str) -> Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k"#;
+    aoc_core::example_tests!(crate::YEAR, 7);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 95437);
-        assert_eq!(part_one().unwrap(), 1232307);
+        let root = parse(&read_input(7).unwrap()).unwrap();
+        assert_eq!(part_one(&root).unwrap(), 1232307);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 24933642);
-        assert_eq!(part_two().unwrap(), 7268994);
+        let root = parse(&read_input(7).unwrap()).unwrap();
+        assert_eq!(part_two(&root).unwrap(), 7268994);
     }
 }
@@ -1,194 +1,107 @@
-use crate::utils::*;
-use derive_more::{Add, Sub};
-use eyre::{ContextCompat, Report, Result};
-use itertools::Itertools;
-use std::{collections::BTreeMap, fs, str::FromStr};
-
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(9, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(9, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
-
-fn part_one_inner(input: &str) -> Result<usize> {
-    let path: RopePath = str::parse(input)?;
-    Ok(path.simulate::<2>()?.len())
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::point::{Direction, Point2};
+use aoc_core::scan;
+use eyre::{Report, Result};
+use std::{collections::BTreeMap, str::FromStr};
+
+pub fn parse(input: &str) -> Result<RopePath> {
+    Ok(str::parse(input)?)
 }
 
-fn part_two_inner(input: &str) -> Result<usize> {
-    let path: RopePath = str::parse(input)?;
-    Ok(path.simulate::<10>()?.len())
+pub fn part_one(path: &RopePath) -> Result<usize> {
+    Ok(path.simulate(2)?.len())
 }
 
-#[derive(Copy, Clone)]
-enum Direction {
-    Right,
-    Left,
-    Up,
-    Down,
+pub fn part_two(path: &RopePath) -> Result<usize> {
+    Ok(path.simulate(10)?.len())
 }
 
+#[derive(Debug)]
 struct Command {
     dir: Direction,
     length: usize,
 }
 
 impl FromStr for Command {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (direction, length) = s
-            .split_once(' ')
-            .wrap_err_with(|| Report::msg(format!("failed to split [{s}]")))?;
-        let length = str::parse(length)?;
-        match direction {
-            "R" => Ok(Command {
-                dir: Direction::Right,
-                length,
-            }),
-            "L" => Ok(Command {
-                dir: Direction::Left,
-                length,
-            }),
-            "U" => Ok(Command {
-                dir: Direction::Up,
-                length,
-            }),
-            "D" => Ok(Command {
-                dir: Direction::Down,
-                length,
-            }),
-            other => Err(Report::msg(format!("unknown direction [{other}]"))),
-        }
+        let (dir, length) = scan!(s, "{direction} {length}" => Direction, usize)?;
+        Ok(Command { dir, length })
     }
 }
 
-struct RopePath {
+#[derive(Debug)]
+pub struct RopePath {
     commands: Vec<Command>,
 }
 
 impl FromStr for RopePath {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let commands: Result<Vec<Command>, _> = s.trim().lines().map(str::parse).collect();
-        let commands = commands?;
+        let commands = parse_lines(s, str::parse)?;
         Ok(RopePath { commands })
     }
 }
 
-#[derive(Add, Sub, Debug, Default, Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl From<Direction> for Point {
-    fn from(value: Direction) -> Self {
-        match value {
-            Direction::Right => Point { x: 1, y: 0 },
-            Direction::Left => Point { x: -1, y: 0 },
-            Direction::Up => Point { x: 0, y: 1 },
-            Direction::Down => Point { x: 0, y: -1 },
-        }
-    }
-}
+type Point = Point2<i32>;
 
 impl RopePath {
-    fn simulate<const N: usize>(&self) -> Result<BTreeMap<Point, usize>> {
-        let mut knots: [Point; N] = [Default::default(); N];
+    /// How many times the tail of a rope of `length` knots visits each point.
+    fn simulate(&self, length: usize) -> Result<BTreeMap<Point, usize>> {
+        if length == 0 {
+            return Err(Report::msg("a rope needs at least one knot"));
+        }
+        let mut knots = vec![Point::default(); length];
         let mut visits = BTreeMap::new();
-        let last = knots.last().unwrap();
-        *visits.entry(*last).or_default() += 1;
+        *visits.entry(knots[length - 1]).or_default() += 1;
         for command in self.commands.iter() {
-            let vector: Point = command.dir.into();
+            let vector: Point = command.dir.offset();
             for _ in 0..command.length {
-                let head = knots.first_mut().unwrap();
-                *head = *head + vector;
-                for (left, right) in (0..N).into_iter().tuple_windows() {
-                    let [left, right] = knots.get_many_mut([left, right])?;
-                    move_tail(left, right);
+                knots[0] += vector;
+                for tail in 1..length {
+                    let head = knots[tail - 1];
+                    move_tail(&head, &mut knots[tail]);
                 }
-                let last = knots.last().unwrap();
-                *visits.entry(*last).or_default() += 1;
+                *visits.entry(knots[length - 1]).or_default() += 1;
             }
         }
         Ok(visits)
     }
 }
 
-fn move_tail(head: &mut Point, tail: &mut Point) {
-    let Point { x, y } = *head - *tail;
-    match (x, y) {
-        (0, y) if y.abs() > 1 => {
-            *tail = *tail
-                + Point {
-                    x: 0,
-                    y: y.signum(),
-                }
-        }
-        (x, 0) if x.abs() > 1 => {
-            *tail = *tail
-                + Point {
-                    x: x.signum(),
-                    y: 0,
-                }
-        }
-        (x, y) if x.abs() > 1 || y.abs() > 1 => {
-            *tail = *tail
-                + Point {
-                    x: x.signum(),
-                    y: y.signum(),
-                }
-        }
-        _ => {}
+fn move_tail(head: &Point, tail: &mut Point) {
+    // the tail only moves once it is no longer touching, and then straight at the head
+    if head.chebyshev(*tail) > 1 {
+        *tail = tail.step_towards(*head);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-"#;
+    aoc_core::example_tests!(crate::YEAR, 9);
 
-    const TEST_INPUT_TWO: &str = r#"
-R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20
-"#;
+    #[test]
+    fn points_at_bad_moves() {
+        let error = parse("R 4\nX 2\n").unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+        let path = parse("R 4\n").unwrap();
+        assert!(path.simulate(0).is_err());
+        assert_eq!(path.simulate(1).unwrap().len(), 5);
+    }
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 13);
-        assert_eq!(part_one().unwrap(), 5878);
+        let path = parse(&read_input(9).unwrap()).unwrap();
+        assert_eq!(part_one(&path).unwrap(), 5878);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 1);
-        assert_eq!(part_two_inner(TEST_INPUT_TWO).unwrap(), 36);
-        assert_eq!(part_two().unwrap(), 2405);
+        let path = parse(&read_input(9).unwrap()).unwrap();
+        assert_eq!(part_two(&path).unwrap(), 2405);
     }
 }
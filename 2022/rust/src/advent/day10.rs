@@ -1,23 +1,8 @@
-use crate::utils::*;
-use eyre::{Report, Result};
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::scan;
+use eyre::Result;
 use itertools::Itertools;
-use std::{fmt::Display, fs, str::FromStr};
-
-pub fn part_one() -> Result<i32> {
-    let input_path = problem_input_path(10, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<String> {
-    let input_path = problem_input_path(10, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
+use std::{fmt::Display, str::FromStr};
 
 #[derive(Debug, Clone)]
 struct Screen {
@@ -50,33 +35,34 @@ impl Display for Screen {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Op {
+pub enum Op {
     Noop,
     AddX(i32),
 }
 
 impl FromStr for Op {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split_whitespace().collect();
-        Ok(match parts.len() {
-            1 => Op::Noop,
-            2 => Op::AddX(str::parse(parts[1])?),
-            _ => panic!("shouldn't be any this size"),
-        })
+        if s == "noop" {
+            Ok(Op::Noop)
+        } else if s.starts_with("addx") {
+            Ok(Op::AddX(scan!(s, "addx {x}" => i32)?.0))
+        } else {
+            Err(ParseError::at(s, 0, "expected noop or addx"))
+        }
     }
 }
 
-struct CPU {
+struct Cpu {
     clock: u32,
     x: i32,
     signal_strengths: Vec<i32>,
     screen: Screen,
 }
 
-impl Default for CPU {
+impl Default for Cpu {
     fn default() -> Self {
-        CPU {
+        Cpu {
             clock: 0,
             x: 1,
             signal_strengths: Vec::new(),
@@ -85,7 +71,7 @@ impl Default for CPU {
     }
 }
 
-impl CPU {
+impl Cpu {
     fn apply(&mut self, op: Op) {
         // TODO: this could be cleaned up and abstracted
         // factor out the containment check and drawing
@@ -125,25 +111,24 @@ impl CPU {
     }
 }
 
-fn parse_instructions(input: &str) -> Result<Vec<Op>> {
-    let ops: Result<Vec<Op>, _> = input.trim().lines().map(str::parse).collect();
-    ops
+type Program = Vec<Op>;
+
+pub fn parse(input: &str) -> Result<Program> {
+    Ok(parse_lines(input, str::parse)?)
 }
 
-fn part_one_inner(input: &str) -> Result<i32> {
-    let ops = parse_instructions(input)?;
-    let mut cpu = CPU::default();
+pub fn part_one(ops: &Program) -> Result<i32> {
+    let mut cpu = Cpu::default();
     for op in ops {
-        cpu.apply(op);
+        cpu.apply(*op);
     }
     Ok(cpu.signal_strengths.iter().sum())
 }
 
-fn part_two_inner(input: &str) -> Result<String> {
-    let ops = parse_instructions(input)?;
-    let mut cpu = CPU::default();
+pub fn part_two(ops: &Program) -> Result<String> {
+    let mut cpu = Cpu::default();
     for op in ops {
-        cpu.apply(op);
+        cpu.apply(*op);
     }
     Ok(cpu.screen.into())
 }
@@ -151,164 +136,9 @@ fn part_two_inner(input: &str) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx -35
-addx 1
-addx 24
-addx -19
-addx 1
-addx 16
-addx -11
-noop
-noop
-addx 21
-addx -15
-noop
-noop
-addx -3
-addx 9
-addx 1
-addx -3
-addx 8
-addx 1
-addx 5
-noop
-noop
-noop
-noop
-noop
-addx -36
-noop
-addx 1
-addx 7
-noop
-noop
-noop
-addx 2
-addx 6
-noop
-noop
-noop
-noop
-noop
-addx 1
-noop
-noop
-addx 7
-addx 1
-noop
-addx -13
-addx 13
-addx 7
-noop
-addx 1
-addx -33
-noop
-noop
-noop
-addx 2
-noop
-noop
-noop
-addx 8
-noop
-addx -1
-addx 2
-addx 1
-noop
-addx 17
-addx -9
-addx 1
-addx 1
-addx -3
-addx 11
-noop
-noop
-addx 1
-noop
-addx 1
-noop
-noop
-addx -13
-addx -19
-addx 1
-addx 3
-addx 26
-addx -30
-addx 12
-addx -1
-addx 3
-addx 1
-noop
-noop
-noop
-addx -9
-addx 18
-addx 1
-addx 2
-noop
-noop
-addx 9
-noop
-noop
-noop
-addx -1
-addx 2
-addx -37
-addx 1
-addx 3
-noop
-addx 15
-addx -21
-addx 22
-addx -6
-addx 1
-noop
-addx 2
-addx 1
-noop
-addx -10
-noop
-noop
-addx 20
-addx 1
-addx 2
-addx 2
-addx -6
-addx -11
-noop
-noop
-noop
-"#;
-
-    const TEST_INPUT_RESULT: &str = r#"
-##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######.....
-"#;
+    aoc_core::example_tests!(crate::YEAR, 10);
 
     const PART_TWO_RESULT: &str = r#"
 ###...##..#....###..###..####..##..#..#.
@@ -320,16 +150,13 @@ noop
 "#;
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 13140);
-        assert_eq!(part_one().unwrap(), 14420);
+        let ops = parse(&read_input(10).unwrap()).unwrap();
+        assert_eq!(part_one(&ops).unwrap(), 14420);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(
-            part_two_inner(TEST_INPUT).unwrap(),
-            TEST_INPUT_RESULT.trim()
-        );
-        assert_eq!(part_two().unwrap(), PART_TWO_RESULT.trim());
+        let ops = parse(&read_input(10).unwrap()).unwrap();
+        assert_eq!(part_two(&ops).unwrap(), PART_TWO_RESULT.trim());
     }
 }
@@ -1,34 +1,22 @@
-use crate::utils::*;
-use eyre::{ContextCompat, Report, Result};
+use aoc_core::parse::{parse_blocks, Cursor, ParseError};
+use eyre::Result;
 use itertools::Itertools;
-use std::{fs, str::FromStr};
-
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(11, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
+use std::str::FromStr;
 
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(11, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<Barrel> {
+    Ok(str::parse(input)?)
 }
 
-fn part_one_inner(input: &str) -> Result<usize> {
-    monkey_business::<20, true>(input)
+pub fn part_one(barrel: &Barrel) -> Result<usize> {
+    monkey_business::<20, true>(barrel)
 }
 
-fn part_two_inner(input: &str) -> Result<usize> {
-    monkey_business::<10000, false>(input)
+pub fn part_two(barrel: &Barrel) -> Result<usize> {
+    monkey_business::<10000, false>(barrel)
 }
 
-fn monkey_business<const ROUNDS: usize, const DIMINISH: bool>(input: &str) -> Result<usize> {
-    let mut barrel: Barrel = str::parse(input)?;
+fn monkey_business<const ROUNDS: usize, const DIMINISH: bool>(barrel: &Barrel) -> Result<usize> {
+    let mut barrel = barrel.clone();
     for _ in 0..ROUNDS {
         barrel.simulate_round::<DIMINISH>();
     }
@@ -42,27 +30,13 @@ fn monkey_business<const ROUNDS: usize, const DIMINISH: bool>(input: &str) -> Re
         .product())
 }
 
+#[derive(Clone)]
 enum Argument {
     Literal(usize),
     Old,
 }
 
-impl FromStr for Argument {
-    type Err = Report;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match str::parse(s) {
-            Ok(lit) => Ok(Argument::Literal(lit)),
-            Err(_) => {
-                if s == "old" {
-                    Ok(Argument::Old)
-                } else {
-                    Err(Report::msg(format!("unknown argument [{s}]")))
-                }
-            }
-        }
-    }
-}
-
+#[derive(Clone)]
 enum Operation {
     Add(Argument),
     Multiply(Argument),
@@ -84,20 +58,27 @@ impl Operation {
     }
 }
 
-impl FromStr for Operation {
-    type Err = Report;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let last_two: Vec<&str> = s.split_whitespace().rev().take(2).collect();
-        let [arg, op]: [&str; 2] = last_two.try_into().unwrap();
-        let arg = str::parse(arg)?;
-        match op {
-            "+" => Ok(Operation::Add(arg)),
-            "*" => Ok(Operation::Multiply(arg)),
-            _ => Err(Report::msg(format!("unknown op [{op}] from [{s}]"))),
-        }
-    }
-}
-
+fn parse_operation(cursor: &mut Cursor) -> Result<Operation, ParseError> {
+    let add = match cursor.peek() {
+        Some('+') => true,
+        Some('*') => false,
+        _ => return Err(cursor.error("expected + or *")),
+    };
+    cursor.next_char();
+    cursor.skip_whitespace();
+    let arg = if cursor.eat("old") {
+        Argument::Old
+    } else {
+        Argument::Literal(cursor.number()?)
+    };
+    Ok(if add {
+        Operation::Add(arg)
+    } else {
+        Operation::Multiply(arg)
+    })
+}
+
+#[derive(Clone)]
 struct Monkey {
     items: Vec<usize>,
     operation: Operation,
@@ -106,28 +87,34 @@ struct Monkey {
     inspection_count: usize,
 }
 
+/// Moves on to the next line, which should start with `prefix` once its indent is skipped.
+fn next_line(cursor: &mut Cursor, prefix: &str) -> Result<(), ParseError> {
+    cursor.skip_whitespace();
+    cursor.expect(prefix)
+}
+
 impl FromStr for Monkey {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        // skip monkey number line
-        lines.next().unwrap();
-        let item_line = lines.next().unwrap();
-        let (_, items) = item_line.split_once(':').wrap_err_with(|| {
-            Report::msg(format!(
-                "Failed to split on : for item line [{item_line}]"
-            ))
-        })?;
-        let items: Result<Vec<usize>, _> = items
-            .split(',')
-            .into_iter()
-            .map(|item| str::parse(item.trim()))
-            .collect();
-        let items = items?;
-        let operation = str::parse(lines.next().unwrap())?;
-        let test = str::parse(lines.next().unwrap().split_whitespace().last().unwrap())?;
-        let true_result = str::parse(lines.next().unwrap().split_whitespace().last().unwrap())?;
-        let false_result = str::parse(lines.next().unwrap().split_whitespace().last().unwrap())?;
+        let mut cursor = Cursor::new(s);
+        next_line(&mut cursor, "Monkey ")?;
+        cursor.number::<usize>()?;
+        cursor.expect(":")?;
+        next_line(&mut cursor, "Starting items: ")?;
+        let mut items = vec![cursor.number()?];
+        while cursor.eat(", ") {
+            items.push(cursor.number()?);
+        }
+        next_line(&mut cursor, "Operation: new = old ")?;
+        let operation = parse_operation(&mut cursor)?;
+        next_line(&mut cursor, "Test: divisible by ")?;
+        let test = cursor.number()?;
+        next_line(&mut cursor, "If true: throw to monkey ")?;
+        let true_result = cursor.number()?;
+        next_line(&mut cursor, "If false: throw to monkey ")?;
+        let false_result = cursor.number()?;
+        cursor.skip_whitespace();
+        cursor.end()?;
         Ok(Monkey {
             items,
             operation,
@@ -138,15 +125,15 @@ impl FromStr for Monkey {
     }
 }
 
-struct Barrel {
+#[derive(Clone)]
+pub struct Barrel {
     monkeys: Vec<Monkey>,
 }
 
 impl FromStr for Barrel {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let monkeys: Result<Vec<Monkey>, _> = s.trim().split("\n\n").map(str::parse).collect();
-        let monkeys = monkeys?;
+        let monkeys = parse_blocks(s, str::parse)?;
         Ok(Barrel { monkeys })
     }
 }
@@ -175,46 +162,27 @@ impl Barrel {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{read_example, read_input};
 
-    const TEST_INPUT: &str = r#"
-Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
-
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1
-"#;
+    aoc_core::example_tests!(crate::YEAR, 11);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 10605);
-        assert_eq!(part_one().unwrap(), 99852);
+        let barrel = parse(&read_input(11).unwrap()).unwrap();
+        assert_eq!(part_one(&barrel).unwrap(), 99852);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 2713310158);
-        assert_eq!(part_two().unwrap(), 25935263541);
+        let barrel = parse(&read_input(11).unwrap()).unwrap();
+        assert_eq!(part_two(&barrel).unwrap(), 25935263541);
+    }
+
+    #[test]
+    fn malformed_monkeys_are_pointed_at() {
+        let input = read_example(11, 1).unwrap().replace("old * 19", "old % 19");
+        let error = str::parse::<Barrel>(&input).err().unwrap();
+        assert_eq!((error.line, error.column), (3, 24));
+        assert_eq!(error.text, "  Operation: new = old % 19");
     }
 }
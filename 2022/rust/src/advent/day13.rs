@@ -1,33 +1,17 @@
-use crate::utils::*;
-use eyre::{Report, Result};
-use std::{fmt::Debug, fs, str::FromStr};
+use aoc_core::parse::{parse_lines, Cursor, ParseError};
+use eyre::{ContextCompat, Result};
+use std::{fmt::Debug, str::FromStr};
 
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(13, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
+type Packets = Vec<SpecialLists>;
 
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(13, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<Packets> {
+    Ok(parse_lines(input, str::parse)?)
 }
 
-fn part_one_inner(input: &str) -> Result<usize> {
-    let pairs = input.trim().split("\n\n");
-    let sum = pairs
-        .into_iter()
-        .map(|pair| {
-            let (left, right) = pair.split_once('\n').unwrap();
-            let left: SpecialLists = str::parse(left).unwrap();
-            let right: SpecialLists = str::parse(right).unwrap();
-            left <= right
-        })
+pub fn part_one(packets: &Packets) -> Result<usize> {
+    let sum = packets
+        .chunks(2)
+        .map(|pair| pair[0] <= pair[1])
         .enumerate()
         .filter(|(_, in_order)| *in_order)
         .map(|(idx, _)| idx + 1)
@@ -35,28 +19,28 @@ fn part_one_inner(input: &str) -> Result<usize> {
     Ok(sum)
 }
 
-fn part_two_inner(input: &str) -> Result<usize> {
+pub fn part_two(packets: &Packets) -> Result<usize> {
     let left_decoder = SpecialLists::List(vec![SpecialLists::List(vec![SpecialLists::Number(2)])]);
     let right_decoder = SpecialLists::List(vec![SpecialLists::List(vec![SpecialLists::Number(6)])]);
-    let mut lists: Vec<SpecialLists> = input
-        .trim()
-        .split("\n\n")
-        .into_iter()
-        .flat_map(|pair| {
-            pair.lines()
-                .map(|line| str::parse::<SpecialLists>(line).unwrap())
-        })
-        .collect();
+    let mut lists = packets.clone();
     lists.push(left_decoder.clone());
     lists.push(right_decoder.clone());
     lists.sort();
-    let left_position = lists.iter().position(|i| *i == left_decoder).unwrap() + 1;
-    let right_position = lists.iter().position(|i| *i == right_decoder).unwrap() + 1;
+    let left_position = lists
+        .iter()
+        .position(|i| *i == left_decoder)
+        .wrap_err("left decoder went missing")?
+        + 1;
+    let right_position = lists
+        .iter()
+        .position(|i| *i == right_decoder)
+        .wrap_err("right decoder went missing")?
+        + 1;
     Ok(left_position * right_position)
 }
 
 #[derive(PartialEq, Eq, Clone)]
-enum SpecialLists {
+pub enum SpecialLists {
     List(Vec<SpecialLists>),
     Number(usize),
 }
@@ -70,35 +54,31 @@ impl Debug for SpecialLists {
     }
 }
 
-fn parse_item(mut input: &str) -> Result<(&str, SpecialLists)> {
-    let item: String = input.chars().take_while(|c| c.is_ascii_digit()).collect();
-    (_, input) = input.split_at(item.len());
-    Ok((input, SpecialLists::Number(str::parse(&item)?)))
-}
-
-fn parse_list(mut input: &str) -> Result<(&str, SpecialLists)> {
-    input = input.strip_prefix('[').unwrap();
-    let mut items = Vec::new();
-    loop {
-        input = input.trim_start_matches(',');
-        let item;
-        if input.starts_with('[') {
-            (input, item) = parse_list(input)?;
-        } else if input.starts_with(']') {
-            return Ok((input.strip_prefix(']').unwrap(), SpecialLists::List(items)));
-        } else {
-            (input, item) = parse_item(input)?;
+fn parse_item(cursor: &mut Cursor) -> Result<SpecialLists, ParseError> {
+    if cursor.eat("[") {
+        let mut items = Vec::new();
+        if !cursor.eat("]") {
+            loop {
+                items.push(parse_item(cursor)?);
+                if cursor.eat("]") {
+                    break;
+                }
+                cursor.expect(",")?;
+            }
         }
-        items.push(item);
+        Ok(SpecialLists::List(items))
+    } else {
+        Ok(SpecialLists::Number(cursor.number()?))
     }
 }
 
 impl FromStr for SpecialLists {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let copy = s.to_owned();
-        let (_, result) = parse_list(&copy)?;
-        Ok(result)
+        let mut cursor = Cursor::new(s);
+        let list = parse_item(&mut cursor)?;
+        cursor.end()?;
+        Ok(list)
     }
 }
 
@@ -136,42 +116,19 @@ impl Ord for SpecialLists {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
-
-[9]
-[[8,7,6]]
-
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
-
-[]
-[3]
-
-[[[]]]
-[[]]
-
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]
-"#;
+    aoc_core::example_tests!(crate::YEAR, 13);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 13);
-        assert_eq!(part_one().unwrap(), 5806);
+        let packets = parse(&read_input(13).unwrap()).unwrap();
+        assert_eq!(part_one(&packets).unwrap(), 5806);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 140);
-        assert_eq!(part_two().unwrap(), 23600);
+        let packets = parse(&read_input(13).unwrap()).unwrap();
+        assert_eq!(part_two(&packets).unwrap(), 23600);
     }
 }
@@ -1,126 +1,90 @@
-use crate::utils::*;
+use aoc_core::parse::parse_lines;
+use aoc_core::point::Point2;
+use aoc_core::sparse_grid::{Bounds, SparseGrid};
 use eyre::{Report, Result};
 use itertools::Itertools;
-use std::{collections::HashMap, fs, str::FromStr};
-
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(14, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+use std::str::FromStr;
+
+pub fn parse(input: &str) -> Result<Grid> {
+    str::parse(input)
 }
 
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(14, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn part_one(grid: &Grid) -> Result<usize> {
+    fill_with_sand(grid, false)
 }
 
-fn part_one_inner(input: &str) -> Result<usize> {
-    let mut grid: Grid = str::parse(input)?;
-    let mut count = 0;
-    while grid.add_sand(false)? {
-        count += 1;
-    }
-    println!("{}", grid.render_grid(false));
-    Ok(count)
+pub fn part_two(grid: &Grid) -> Result<usize> {
+    fill_with_sand(grid, true)
 }
 
-fn part_two_inner(input: &str) -> Result<usize> {
-    let mut grid: Grid = str::parse(input)?;
+fn fill_with_sand(grid: &Grid, with_floor: bool) -> Result<usize> {
+    let mut grid = grid.clone();
     let mut count = 0;
-    while grid.add_sand(true)? {
+    while grid.add_sand(with_floor)? {
         count += 1;
     }
-    println!("{}", grid.render_grid(true));
     Ok(count)
 }
 
 struct Path {
-    points: Vec<(isize, isize)>,
+    points: Vec<Point2<i64>>,
 }
 
 impl FromStr for Path {
     type Err = Report;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let points: Vec<(isize, isize)> = s
-            .split("->")
-            .map(|point| {
-                let (left, right) = point.trim().split_once(',').unwrap();
-                (str::parse(left).unwrap(), str::parse(right).unwrap())
-            })
-            .collect();
-        Ok(Path { points })
+        let points: Result<Vec<Point2<i64>>, _> = s.split("->").map(str::parse).collect();
+        Ok(Path { points: points? })
     }
 }
 
 impl Path {
-    fn points(&self) -> impl Iterator<Item = (isize, isize)> {
+    fn points(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
         self.points
-            .clone()
-            .into_iter()
+            .iter()
             .tuple_windows()
-            .flat_map(|(left, right)| {
-                let diff = (right.0 - left.0, right.1 - left.1);
-                let unit_vector = (diff.0.signum(), diff.1.signum());
-                let times = diff.0.abs().max(diff.1.abs());
-                let mut points = vec![left];
-                for _ in 0..times {
-                    let previous = points.last().unwrap();
-                    let next = (previous.0 + unit_vector.0, previous.1 + unit_vector.1);
-                    points.push(next);
-                }
-                points.into_iter()
-            })
+            .flat_map(|(&left, &right)| left.line_to(right))
+            .map(Into::into)
     }
 }
 
+#[derive(Clone)]
 enum Material {
     Sand,
     Rock,
 }
 
-struct Grid {
-    contents: HashMap<(isize, isize), Material>,
-    bounds: ((isize, isize), (isize, isize)),
+#[derive(Clone)]
+pub struct Grid {
+    contents: SparseGrid<Material>,
+    /// The depth of the lowest rock, which sand falls past into the abyss.
+    lowest_rock: i64,
 }
 
 impl FromStr for Grid {
     type Err = Report;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let paths: Result<Vec<Path>, _> = s.trim().lines().map(str::parse).collect();
-        let mut contents = HashMap::new();
-        let mut bounds = ((Grid::SAND_SPOUT.0, 0), (Grid::SAND_SPOUT.0, 0));
-        for path in paths?.into_iter() {
-            for point in path.points() {
-                contents.insert(point, Material::Rock);
-                if point.0 < bounds.0 .0 {
-                    bounds.0 .0 = point.0;
-                }
-                // omit finding top left point's y since it'll be 0
-                if point.0 > bounds.1 .0 {
-                    bounds.1 .0 = point.0;
-                }
-                if point.1 > bounds.1 .1 {
-                    bounds.1 .1 = point.1;
-                }
-            }
+        let paths: Vec<Path> = parse_lines(s, str::parse)?;
+        let mut contents = SparseGrid::new();
+        for path in paths.into_iter() {
+            contents.extend(path.points().map(|point| (point, Material::Rock)));
         }
-        Ok(Grid { contents, bounds })
+        let lowest_rock = contents.bounds().map_or(0, |bounds| bounds.max.1);
+        Ok(Grid {
+            contents,
+            lowest_rock,
+        })
     }
 }
 
 impl Grid {
-    const SAND_SPOUT: (isize, isize) = (500, 0);
+    const SAND_SPOUT: (i64, i64) = (500, 0);
 
-    fn _contains(&self, key: &(isize, isize), with_floor: bool) -> bool {
-        if with_floor && key.1 >= self.bounds.1 .1 + 2 {
+    fn _contains(&self, key: &(i64, i64), with_floor: bool) -> bool {
+        if with_floor && key.1 >= self.lowest_rock + 2 {
             return true;
         }
-        self.contents.contains_key(key)
+        self.contents.contains(*key)
     }
 
     fn add_sand(&mut self, with_floor: bool) -> Result<bool> {
@@ -145,54 +109,53 @@ impl Grid {
             } else {
                 y += 1;
             }
-            if !with_floor && y >= self.bounds.1 .1 {
+            if !with_floor && y >= self.lowest_rock {
                 return Ok(false);
             }
         }
     }
 
-    fn render_grid(&self, with_floor: bool) -> String {
-        (self.bounds.0 .1..=(self.bounds.1 .1 + if with_floor { 2 } else { 0 }))
-            .into_iter()
-            .map(|y| {
-                (self.bounds.0 .0..=self.bounds.1 .0)
-                    .into_iter()
-                    .map(|x| {
-                        if y == self.bounds.1 .1 + 2 {
-                            '#'
-                        } else if let Some(material) = self.contents.get(&(x, y)) {
-                            match material {
-                                Material::Rock => '#',
-                                Material::Sand => 'o',
-                            }
-                        } else {
-                            '.'
-                        }
-                    })
-                    .join("")
-            })
-            .join("\n")
+    fn _render_grid(&self, with_floor: bool) -> String {
+        let mut viewport = self
+            .contents
+            .bounds()
+            .unwrap_or(Bounds::point(Self::SAND_SPOUT));
+        viewport.include(Self::SAND_SPOUT);
+        let mut rendered = self.contents.render(viewport, |material| match material {
+            Some(Material::Rock) => '#',
+            Some(Material::Sand) => 'o',
+            None => '.',
+        });
+        if with_floor {
+            // the floor is infinite, so it is drawn rather than stored
+            let gap = self.lowest_rock + 1 - viewport.max.1;
+            for _ in 0..gap {
+                rendered.push_str(&".".repeat(viewport.width()));
+                rendered.push('\n');
+            }
+            rendered.push_str(&"#".repeat(viewport.width()));
+            rendered.push('\n');
+        }
+        rendered
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9
-"#;
+    aoc_core::example_tests!(crate::YEAR, 14);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 24);
-        assert_eq!(part_one().unwrap(), 696);
+        let grid = parse(&read_input(14).unwrap()).unwrap();
+        assert_eq!(part_one(&grid).unwrap(), 696);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 93);
-        assert_eq!(part_two().unwrap(), 23610);
+        let grid = parse(&read_input(14).unwrap()).unwrap();
+        assert_eq!(part_two(&grid).unwrap(), 23610);
     }
 }
@@ -1,41 +1,27 @@
-use crate::utils::*;
-use derivative::Derivative;
-use eyre::{Report, Result};
-use itertools::Itertools;
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::scan;
+use aoc_core::search::bfs;
+use eyre::{ContextCompat, Result};
 use std::{
-    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
-    fs,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, VecDeque},
     str::FromStr,
 };
 
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(16, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
-
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(16, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<ValveSystem> {
+    Ok(str::parse(input)?)
 }
 
 // this computes the best single path using an A* like technique
 // it is not appropriate for Part 2 because we expect to take a suboptimal route for part 2
 // the optimal route for Part 2 would be more like taking every other next best step, disjointly
 // it would be faster if we switched to bitsets to reduce memory pressure/copying
-fn part_one_inner(input: &str) -> Result<usize> {
-    let valve_system: ValveSystem = str::parse(input)?;
-    let compact_paths = compact(&valve_system.connections, &valve_system.flow_rates);
+pub fn part_one(valve_system: &ValveSystem) -> Result<usize> {
+    let compact_paths = compact(&valve_system.connections, &valve_system.flow_rates)?;
     let mut frontier = BinaryHeap::new();
     let initial = Path {
         location: "AA".to_owned(),
         time_remaining: 30,
-        unvisited: valve_system.flow_rates,
+        unvisited: valve_system.flow_rates.clone(),
         pressure: 0,
         released: 0,
     };
@@ -75,9 +61,8 @@ fn part_one_inner(input: &str) -> Result<usize> {
 
 // for part 2, we need to compute all routes (at least of a certain quality)
 // then find the two disjoint routes that perform best
-fn part_two_inner(input: &str) -> Result<usize> {
-    let valve_system: ValveSystem = str::parse(input)?;
-    let compact_paths = compact(&valve_system.connections, &valve_system.flow_rates);
+pub fn part_two(valve_system: &ValveSystem) -> Result<usize> {
+    let compact_paths = compact(&valve_system.connections, &valve_system.flow_rates)?;
     let mut complete_paths: HashMap<BTreeSet<Valve>, usize> = HashMap::new();
     let mut frontier = VecDeque::new();
     let initial = Path {
@@ -92,26 +77,24 @@ fn part_two_inner(input: &str) -> Result<usize> {
     // thats why they store the max for any combination they've found - they don't care the path
     // TODO: optimize
     while let Some(path) = frontier.pop_front() {
-        // TODO: add "if empty" branch so it works for the test input
+        // any route can stop here and leave the rest to the other worker, with the valves it has
+        // opened releasing until time runs out
+        let opened = valve_system
+            .flow_rates
+            .difference(&path.unvisited)
+            .cloned()
+            .collect();
+        let released = path.released + path.pressure * path.time_remaining;
+        let combination_best = complete_paths.entry(opened).or_default();
+        if *combination_best < released {
+            *combination_best = released;
+        }
         for target in path.unvisited.iter() {
             let route = format!("{}{}", path.location, target.name);
             let cost = compact_paths[&route];
-            let mut path_clone = path.clone();
-            path_clone.released += path_clone.pressure * usize::min(path.time_remaining, cost);
-            if cost >= path_clone.time_remaining {
-                let combination_best = complete_paths
-                    .entry(
-                        valve_system
-                            .flow_rates
-                            .difference(&path_clone.unvisited)
-                            .cloned()
-                            .collect(),
-                    )
-                    .or_default();
-                if *combination_best < path_clone.released {
-                    *combination_best = path_clone.released;
-                }
-            } else {
+            if cost < path.time_remaining {
+                let mut path_clone = path.clone();
+                path_clone.released += path_clone.pressure * cost;
                 path_clone.time_remaining -= cost;
                 path_clone.pressure += target.pressure;
                 path_clone.location = target.name.clone();
@@ -132,55 +115,32 @@ fn part_two_inner(input: &str) -> Result<usize> {
     Ok(best)
 }
 
+/// The minutes it takes to walk from each valve worth opening (and the start) to every other
+/// such valve and open it.
 fn compact(
     connections: &HashMap<String, Vec<String>>,
     targets: &BTreeSet<Valve>,
-) -> BTreeMap<String, usize> {
+) -> Result<BTreeMap<String, usize>> {
     let mut compact = BTreeMap::new();
     let starting_location = "AA".to_owned();
-    for target in targets.iter() {
-        let best_path = best_path(&starting_location, &target.name, connections);
-        compact.insert(
-            format!("{}{}", starting_location, target.name),
-            best_path.len(),
+    let sources = std::iter::once(&starting_location).chain(targets.iter().map(|t| &t.name));
+    for from in sources {
+        let explored = bfs(
+            from,
+            |&location| connections.get(location).into_iter().flatten(),
+            |_| false,
         );
-    }
-    for pair in targets.iter().permutations(2) {
-        let [from, to]: [&Valve; 2] = pair.try_into().unwrap();
-        let best_path = best_path(&from.name, &to.name, connections);
-        compact.insert(format!("{}{}", from.name, to.name), best_path.len());
-    }
-    compact
-}
-
-fn best_path(
-    from: &String,
-    to: &String,
-    connections: &HashMap<String, Vec<String>>,
-) -> Vec<String> {
-    let mut seen = HashSet::new();
-    let mut frontier = VecDeque::new();
-    seen.insert(from);
-    frontier.push_back(vec![from]);
-    while let Some(mut path) = frontier.pop_front() {
-        let last = path.last().unwrap();
-        if let Some(edges) = connections.get(*last) {
-            for neighbor in edges.iter() {
-                if neighbor == to {
-                    path.push(to);
-                    return path.into_iter().cloned().collect();
-                } else if seen.insert(neighbor) {
-                    let mut path_clone = path.clone();
-                    path_clone.push(neighbor);
-                    frontier.push_back(path_clone);
-                }
-            }
+        for to in targets.iter().filter(|to| &to.name != from) {
+            let distance = explored
+                .distance(&&to.name)
+                .wrap_err_with(|| format!("no tunnels lead from {from} to {}", to.name))?;
+            compact.insert(format!("{}{}", from, to.name), distance + 1);
         }
     }
-    unreachable!()
+    Ok(compact)
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
+#[allow(clippy::derived_hash_with_manual_eq)]
 #[derive(Debug, Clone, Eq, Hash)]
 struct Path {
     location: String,
@@ -193,34 +153,33 @@ struct Path {
 impl Path {
     fn heuristic(&self, compact_paths: &BTreeMap<String, usize>) -> usize {
         let project_past = self.pressure * self.time_remaining;
-        if self.unvisited.is_empty() {
-            project_past + self.pressure * self.time_remaining
-        } else {
-            let realistic_start =
-                format!("{}{}", self.location, self.unvisited.first().unwrap().name);
-            let starting_cost = compact_paths[&realistic_start];
-            let (time_used, optimistic_future): (usize, usize) = self
-                .unvisited
-                .iter()
-                .rev()
-                .enumerate()
-                .map(|(idx, valve)| {
-                    let time_used = if idx > 0 { idx } else { starting_cost };
-                    let time = self
-                        .time_remaining
-                        .saturating_sub(2 * (idx + starting_cost));
-                    if time > 0 {
-                        (time_used, time * valve.pressure)
-                    } else {
-                        (0, 0)
-                    }
-                })
-                .reduce(|(time_used, opt_future), (t, v)| (time_used + t, opt_future + v))
-                .unwrap();
-            project_past
-                + optimistic_future
-                + self.time_remaining.saturating_sub(time_used) * self.pressure
-        }
+        let Some(nearest) = self.unvisited.first() else {
+            return project_past + self.pressure * self.time_remaining;
+        };
+        let realistic_start = format!("{}{}", self.location, nearest.name);
+        let starting_cost = compact_paths[&realistic_start];
+        let (time_used, optimistic_future): (usize, usize) = self
+            .unvisited
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(idx, valve)| {
+                let time_used = if idx > 0 { idx } else { starting_cost };
+                let time = self
+                    .time_remaining
+                    .saturating_sub(2 * (idx + starting_cost));
+                if time > 0 {
+                    (time_used, time * valve.pressure)
+                } else {
+                    (0, 0)
+                }
+            })
+            .fold((0, 0), |(time_used, opt_future), (t, v)| {
+                (time_used + t, opt_future + v)
+            });
+        project_past
+            + optimistic_future
+            + self.time_remaining.saturating_sub(time_used) * self.pressure
     }
 }
 
@@ -232,63 +191,60 @@ impl PartialEq for Path {
 
 impl PartialOrd for Path {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.released.cmp(&other.released))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Path {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        self.released.cmp(&other.released)
     }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
-#[derive(Debug, Clone, Derivative, Hash)]
-#[derivative(PartialEq, Eq, PartialOrd, Ord)]
+/// Valves order by pressure, so the most valuable are last. The name only breaks ties, but it
+/// must, or valves with the same flow rate would be taken for one another.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Valve {
-    #[derivative(PartialEq = "ignore")]
-    #[derivative(PartialOrd = "ignore")]
-    #[derivative(Ord = "ignore")]
-    name: String,
     pressure: usize,
+    name: String,
 }
 
 #[derive(Debug)]
-struct ValveSystem {
+pub struct ValveSystem {
     flow_rates: BTreeSet<Valve>,
     connections: HashMap<String, Vec<String>>,
 }
 
+/// A valve, and the valves its tunnels lead to.
+fn parse_valve(line: &str) -> Result<(Valve, Vec<String>), ParseError> {
+    let (name, pressure, tunnels) = scan!(
+        line,
+        "Valve {name} has flow rate={rate}; {tunnels}" => String, usize, String
+    )?;
+    let others = ["tunnels lead to valves ", "tunnel leads to valve "]
+        .iter()
+        .find_map(|prefix| tunnels.strip_prefix(prefix))
+        .ok_or_else(|| {
+            ParseError::at(
+                line,
+                line.len() - tunnels.len(),
+                "expected the valves the tunnels lead to",
+            )
+        })?;
+    let others = others.split(',').map(|v| v.trim().to_owned()).collect();
+    Ok((Valve { name, pressure }, others))
+}
+
 impl FromStr for ValveSystem {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut flow_rates = BTreeSet::new();
         let mut connections = HashMap::new();
-        for line in s.trim().lines() {
-            let (valve, line) = line
-                .strip_prefix("Valve ")
-                .unwrap()
-                .split_once(' ')
-                .unwrap();
-            let (flow_rate, line) = line
-                .strip_prefix("has flow rate=")
-                .unwrap()
-                .split_once(';')
-                .unwrap();
-            let (_, line) = line.split_once("valve").unwrap();
-            let other: Vec<String> = line
-                .trim_start_matches('s')
-                .split(',')
-                .map(|v| v.trim().to_owned())
-                .collect();
-            let flow_rate = str::parse(flow_rate)?;
-            if flow_rate > 0 {
-                flow_rates.insert(Valve {
-                    name: valve.to_owned(),
-                    pressure: flow_rate,
-                });
+        for (valve, others) in parse_lines(s, parse_valve)? {
+            connections.insert(valve.name.clone(), others);
+            if valve.pressure > 0 {
+                flow_rates.insert(valve);
             }
-            connections.insert(valve.into(), other);
         }
         Ok(ValveSystem {
             flow_rates,
@@ -300,31 +256,145 @@ impl FromStr for ValveSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
+    use aoc_core::differential::{check_agreement, Solver};
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    aoc_core::example_tests!(crate::YEAR, 16);
+
+    /// The most pressure `actors` working together can release in `minutes`, found by trying
+    /// every set of valves each could open in every order. Slow, but it shares only the line
+    /// parser with the solver.
+    fn exhaustive(input: &str, minutes: usize, actors: usize) -> Result<usize> {
+        let valves = parse_lines(input, parse_valve)?;
+        let index: HashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, (valve, _))| (valve.name.as_str(), i))
+            .collect();
+        // minutes to walk between any two valves, by Floyd-Warshall
+        let n = valves.len();
+        let mut distance = vec![vec![usize::MAX / 2; n]; n];
+        for (i, (_, others)) in valves.iter().enumerate() {
+            distance[i][i] = 0;
+            for other in others {
+                distance[i][index[other.as_str()]] = 1;
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    distance[i][j] = distance[i][j].min(distance[i][k] + distance[k][j]);
+                }
+            }
+        }
+        let useful: Vec<usize> = (0..n).filter(|&i| valves[i].0.pressure > 0).collect();
+
+        // the best release for each set of opened valves, by one actor alone
+        let mut best = vec![0; 1 << useful.len()];
+        let mut stack = vec![(index["AA"], minutes, 0usize, 0)];
+        while let Some((at, remaining, opened, released)) = stack.pop() {
+            best[opened] = best[opened].max(released);
+            for (bit, &valve) in useful.iter().enumerate() {
+                let cost = distance[at][valve] + 1;
+                if opened & (1 << bit) == 0 && cost < remaining {
+                    let left = remaining - cost;
+                    let released = released + left * valves[valve].0.pressure;
+                    stack.push((valve, left, opened | (1 << bit), released));
+                }
+            }
+        }
+        Ok(match actors {
+            1 => best.iter().copied().max().unwrap_or(0),
+            _ => (0..best.len())
+                .flat_map(|a| {
+                    (0..best.len())
+                        .filter(move |b| a & b == 0)
+                        .map(move |b| (a, b))
+                })
+                .map(|(a, b)| best[a] + best[b])
+                .max()
+                .unwrap_or(0),
+        })
+    }
 
-    const TEST_INPUT: &str = r#"
-Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II
-"#;
+    /// Connected tunnel networks starting at a stuck valve `AA`, written out as puzzle input.
+    fn valve_systems() -> impl Strategy<Value = String> {
+        (2..8usize)
+            .prop_flat_map(|n| {
+                let rates = vec(prop_oneof![Just(0usize), 1..30usize], n - 1);
+                // each valve after the first has a tunnel back to an earlier one, so all are
+                // reachable, plus a few extra tunnels between any two
+                let tree = (1..n).map(|i| 0..i).collect::<Vec<_>>();
+                let extra = vec((0..n, 0..n), 0..n);
+                (rates, tree, extra)
+            })
+            .prop_map(|(rates, tree, extra)| {
+                let n = rates.len() + 1;
+                let name = |i: usize| {
+                    let letter = (b'A' + i as u8) as char;
+                    format!("{letter}{letter}")
+                };
+                let mut tunnels = vec![BTreeSet::new(); n];
+                let edges = tree.into_iter().enumerate().map(|(i, j)| (i + 1, j));
+                for (a, b) in edges.chain(extra).filter(|(a, b)| a != b) {
+                    tunnels[a].insert(name(b));
+                    tunnels[b].insert(name(a));
+                }
+                let rates = std::iter::once(0).chain(rates);
+                rates
+                    .zip(tunnels)
+                    .enumerate()
+                    .map(|(i, (rate, others))| {
+                        let others = others.into_iter().collect::<Vec<_>>().join(", ");
+                        let plural = if others.contains(',') {
+                            "s lead to valves"
+                        } else {
+                            " leads to valve"
+                        };
+                        format!(
+                            "Valve {} has flow rate={rate}; tunnel{plural} {others}\n",
+                            name(i)
+                        )
+                    })
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn solvers_agree() {
+        check_agreement(
+            crate::YEAR,
+            16,
+            64,
+            valve_systems(),
+            &[
+                Solver::new("search", |input| part_one(&parse(input)?)),
+                Solver::new("exhaustive", |input| exhaustive(input, 30, 1)),
+            ],
+        );
+        check_agreement(
+            crate::YEAR,
+            16,
+            64,
+            valve_systems(),
+            &[
+                Solver::new("search", |input| part_two(&parse(input)?)),
+                Solver::new("exhaustive", |input| exhaustive(input, 26, 2)),
+            ],
+        );
+    }
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 1651);
-        assert_eq!(part_one().unwrap(), 2087);
+        let valve_system = parse(&read_input(16).unwrap()).unwrap();
+        assert_eq!(part_one(&valve_system).unwrap(), 2087);
     }
 
     #[test]
     fn part_two_works() {
-        // See TODO above. The real input cannot visit all valves so I omitted the empty target case.
-        // But the test input is easy to visit all so the solver just returns the best single route.
-        // assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 1707);
-        assert_eq!(part_two().unwrap(), 2591);
+        let valve_system = parse(&read_input(16).unwrap()).unwrap();
+        assert_eq!(part_two(&valve_system).unwrap(), 2591);
     }
 }
@@ -1,95 +1,90 @@
-use crate::utils::*;
-use eyre::{Report, Result};
+use aoc_core::cycle::find_cycle;
+use aoc_core::parse::ParseError;
+use eyre::{ContextCompat, Report, Result};
 use itertools::Itertools;
-use std::{collections::BTreeMap, fmt::Display, fs};
+use std::fmt::Display;
 
-pub fn part_one() -> Result<i128> {
-    let input_path = problem_input_path(17, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+type Jets = Vec<Direction>;
+
+pub fn part_one(directions: &Jets) -> Result<i64> {
+    let mut chamber = Chamber::new(directions)?;
+    for _ in 0..2022 {
+        chamber.drop_piece();
+    }
+    Ok(chamber.height())
 }
 
-pub fn part_two() -> Result<i128> {
-    let input_path = problem_input_path(17, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn part_two(directions: &Jets) -> Result<i64> {
+    const GOAL: usize = 1_000_000_000_000;
+    let mut chamber = Chamber::new(directions)?;
+    let (cycle, heights) = find_cycle(
+        &mut chamber,
+        Chamber::drop_piece,
+        Chamber::state,
+        Chamber::height,
+    );
+    cycle
+        .extrapolate(&heights, GOAL)
+        .wrap_err("the tunnel did not grow for a whole cycle")
 }
 
-fn part_one_inner(input: &str) -> Result<i128> {
-    let directions = parse_input(input);
-    let mut directions = directions.into_iter().cycle();
-    let mut tunnel = Tunnel::default();
-    let mut pieces = Piece::iter().map(|piece| piece.into());
-    tunnel.add_piece(pieces.next().unwrap());
-    let mut count = 1;
-    while count <= 2022 {
-        if tunnel.simulate_direction(directions.next().unwrap()) {
-            tunnel.add_piece(pieces.next().unwrap());
-            count += 1;
+/// The tunnel along with where the jet pattern and the sequence of pieces are up to.
+struct Chamber<'a> {
+    tunnel: Tunnel,
+    jets: &'a [Direction],
+    jet: usize,
+    piece: usize,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a [Direction]) -> Result<Self> {
+        if jets.is_empty() {
+            return Err(Report::msg("no jets of hot gas"));
         }
+        Ok(Chamber {
+            tunnel: Tunnel::default(),
+            jets,
+            jet: 0,
+            piece: 0,
+        })
     }
-    Ok(tunnel.highest + 1)
-}
 
-fn part_two_inner(input: &str) -> Result<i128> {
-    const GOAL: i128 = 1_000_000_000_000;
-    let directions = parse_input(input);
-    let mut directions = directions.into_iter().enumerate().cycle();
-    let mut tunnel = Tunnel::default();
-    let mut pieces = Piece::iter().map(|piece| piece.into());
-    tunnel.add_piece(pieces.next().unwrap());
-    let mut count: i128 = 1;
-    let mut seen_states = BTreeMap::new();
-    let mut count_heights = BTreeMap::new();
-    while count <= GOAL {
-        let state = tunnel.state_snapshot();
-        let (move_idx, next_move) = directions.next().unwrap();
-        if let Some((prior_count, prior_highest)) =
-            seen_states.insert((move_idx, state), (count, tunnel.highest))
-        {
-            let elapsed = count - prior_count;
-            if elapsed > 0 {
-                println!("Found a cycle! {prior_count} to {count}");
-                let remaining = dbg!(GOAL - count);
-                let fast_forward_cycles = dbg!(remaining / elapsed);
-                let growth = dbg!(tunnel.highest - prior_highest);
-                tunnel.highest += dbg!(growth * fast_forward_cycles);
-                count += dbg!(elapsed * fast_forward_cycles);
-                let remaining_after_fast_forward = GOAL - count;
-                let prior_difference_highest =
-                    count_heights[&(prior_count + remaining_after_fast_forward)] - prior_highest;
-                return Ok(tunnel.highest + prior_difference_highest + 1);
+    /// Drops the next piece, pushing it with the jets until it comes to rest.
+    fn drop_piece(&mut self) {
+        self.tunnel.add_piece(Piece::ORDER[self.piece].into());
+        self.piece = (self.piece + 1) % Piece::ORDER.len();
+        loop {
+            let direction = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            if self.tunnel.simulate_direction(direction) {
+                return;
             }
         }
-        if tunnel.simulate_direction(next_move) {
-            tunnel.add_piece(pieces.next().unwrap());
-            // println!("{}:\n{}", count, tunnel);
-            count_heights.insert(count, tunnel.highest);
-            count += 1;
-        }
     }
-    Ok(tunnel.highest + 1)
+
+    fn height(&self) -> i64 {
+        (self.tunnel.highest + 1) as i64
+    }
+
+    /// Everything that decides how the tunnel grows from here on.
+    fn state(&self) -> (usize, usize, u128) {
+        (self.jet, self.piece, self.tunnel.state_snapshot())
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Direction> {
-    let directions: Result<Vec<Direction>, _> = input
-        .chars()
-        .map(|c| {
-            Ok(match c {
-                '<' => Direction::Left,
-                '>' => Direction::Right,
-                _ => return Err(Report::msg(format!("Bad character [{c}]"))),
-            })
+pub fn parse(input: &str) -> Result<Jets> {
+    let input = input.trim();
+    input
+        .char_indices()
+        .map(|(offset, c)| match c {
+            '<' => Ok(Direction::Left),
+            '>' => Ok(Direction::Right),
+            _ => Err(ParseError::at(input, offset, format!("expected < or >, found {c:?}")).into()),
         })
-        .collect();
-    directions.unwrap()
+        .collect()
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 enum Piece {
     Flat,
     Plus,
@@ -98,24 +93,20 @@ enum Piece {
     Square,
 }
 
-#[derive(Clone)]
-enum Direction {
+#[derive(Clone, Copy)]
+pub enum Direction {
     Left,
     Right,
 }
 
 impl Piece {
-    fn iter() -> impl Iterator<Item = Piece> {
-        vec![
-            Piece::Flat,
-            Piece::Plus,
-            Piece::L,
-            Piece::Straight,
-            Piece::Square,
-        ]
-        .into_iter()
-        .cycle()
-    }
+    const ORDER: [Piece; 5] = [
+        Piece::Flat,
+        Piece::Plus,
+        Piece::L,
+        Piece::Straight,
+        Piece::Square,
+    ];
 }
 
 impl From<Piece> for Vec<u8> {
@@ -147,7 +138,7 @@ impl Tunnel {
         }
     }
 
-    fn tunnel_collision(&self, piece: &Vec<u8>, depth: usize) -> bool {
+    fn tunnel_collision(&self, piece: &[u8], depth: usize) -> bool {
         piece
             .iter()
             .rev()
@@ -157,7 +148,7 @@ impl Tunnel {
 
     fn move_left(&mut self) {
         let wall_collision = self.piece.iter().any(|r| (r & 0b100_0000) > 0);
-        let candidate = self.piece.iter().map(|r| r << 1).collect();
+        let candidate: Vec<u8> = self.piece.iter().map(|r| r << 1).collect();
         let tunnel_collision = self.tunnel_collision(&candidate, self.piece_bottom);
         if !wall_collision && !tunnel_collision {
             self.piece = candidate;
@@ -165,7 +156,7 @@ impl Tunnel {
     }
     fn move_right(&mut self) {
         let wall_collision = self.piece.iter().any(|r| (r & 0b000_0001) > 0);
-        let candidate = self.piece.iter().map(|r| r >> 1).collect();
+        let candidate: Vec<u8> = self.piece.iter().map(|r| r >> 1).collect();
         let tunnel_collision = self.tunnel_collision(&candidate, self.piece_bottom);
         if !wall_collision && !tunnel_collision {
             self.piece = candidate;
@@ -246,18 +237,39 @@ impl Default for Tunnel {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{read_example, read_input};
 
-    const TEST_INPUT: &str = r#">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"#;
+    aoc_core::example_tests!(crate::YEAR, 17);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 3068);
-        assert_eq!(part_one().unwrap(), 3130);
+        let jets = parse(&read_input(17).unwrap()).unwrap();
+        assert_eq!(part_one(&jets).unwrap(), 3130);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 1_514_285_714_288);
-        assert_eq!(part_two().unwrap(), 1556521739139);
+        let jets = parse(&read_input(17).unwrap()).unwrap();
+        assert_eq!(part_two(&jets).unwrap(), 1556521739139);
+    }
+
+    #[test]
+    fn tunnel_cycles() {
+        let jets = parse(&read_example(17, 1).unwrap()).unwrap();
+        let mut chamber = Chamber::new(&jets).unwrap();
+        let (cycle, heights) = find_cycle(
+            &mut chamber,
+            Chamber::drop_piece,
+            Chamber::state,
+            Chamber::height,
+        );
+        assert_eq!(cycle.period, 35);
+        // every height past the cycle found agrees with simply simulating that far
+        let mut chamber = Chamber::new(&jets).unwrap();
+        for pieces in 1..=2022 {
+            chamber.drop_piece();
+            assert_eq!(cycle.extrapolate(&heights, pieces), Some(chamber.height()));
+        }
+        assert_eq!(cycle.extrapolate(&heights, 2022), Some(3068));
     }
 }
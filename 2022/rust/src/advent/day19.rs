@@ -1,43 +1,26 @@
-use crate::utils::*;
-use eyre::{Report, Result};
+use aoc_core::parse::{parse_lines, ParseError};
+use aoc_core::scan;
+use eyre::Result;
 use rayon::prelude::*;
 use std::{
-    fs,
     ops::{Add, Mul, Sub},
     str::FromStr,
 };
 
-pub fn part_one() -> Result<usize> {
-    let input_path = problem_input_path(19, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
+type Blueprints = Vec<Blueprint>;
 
-pub fn part_two() -> Result<usize> {
-    let input_path = problem_input_path(19, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<Blueprints> {
+    Ok(parse_lines(input, str::parse)?)
 }
 
-fn part_one_inner(input: &str) -> Result<usize> {
-    let blueprints: Result<Vec<Blueprint>, _> =
-        input.trim().lines().map(str::parse::<Blueprint>).collect();
-    Ok(blueprints?.into_par_iter().map(|b| b.quality_score()).sum())
+pub fn part_one(blueprints: &Blueprints) -> Result<usize> {
+    Ok(blueprints.par_iter().map(|b| b.quality_score()).sum())
 }
 
-fn part_two_inner(input: &str) -> Result<usize> {
-    let blueprints: Result<Vec<Blueprint>, _> = input
-        .trim()
-        .lines()
-        .map(str::parse::<Blueprint>)
+pub fn part_two(blueprints: &Blueprints) -> Result<usize> {
+    Ok(blueprints
+        .iter()
         .take(3)
-        .collect();
-    Ok(blueprints?
-        .into_iter()
         .map(|b| b.geodes_harvested(32))
         .product())
 }
@@ -105,7 +88,7 @@ impl State {
 }
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
     id: usize,
     ore_bot_cost: Resources,
     clay_bot_cost: Resources,
@@ -284,18 +267,17 @@ impl Blueprint {
 }
 
 impl FromStr for Blueprint {
-    type Err = Report;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (id_str, rest) = s.trim().split_once(':').unwrap();
-        let (_, id_str) = id_str.split_once(' ').unwrap();
-        let id = str::parse(id_str).unwrap();
-        let values: Vec<usize> = rest
-            .trim()
-            .split_ascii_whitespace()
-            .filter_map(|maybe_number| str::parse::<usize>(maybe_number).ok())
-            .collect();
-        let [ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian]: [usize;
-            6] = values.try_into().unwrap();
+        let (id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian) = scan!(
+            s.trim(),
+            "Blueprint {id}: \
+             Each ore robot costs {ore} ore. \
+             Each clay robot costs {ore} ore. \
+             Each obsidian robot costs {ore} ore and {clay} clay. \
+             Each geode robot costs {ore} ore and {obsidian} obsidian."
+            => usize, usize, usize, usize, usize, usize, usize
+        )?;
         Ok(Blueprint {
             id,
             ore_bot_cost: Resources {
@@ -329,21 +311,19 @@ impl FromStr for Blueprint {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
-"#;
+    aoc_core::example_tests!(crate::YEAR, 19);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 33);
-        assert_eq!(part_one().unwrap(), 1480);
+        let blueprints = parse(&read_input(19).unwrap()).unwrap();
+        assert_eq!(part_one(&blueprints).unwrap(), 1480);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 56 * 62);
-        assert_eq!(part_two().unwrap(), 3168);
+        let blueprints = parse(&read_input(19).unwrap()).unwrap();
+        assert_eq!(part_two(&blueprints).unwrap(), 3168);
     }
 }
@@ -1,66 +1,70 @@
-use crate::utils::*;
-use eyre::Result;
-use itertools::Itertools;
-use std::{
-    collections::{BTreeMap, VecDeque},
-    fs,
-};
+use aoc_core::parse::parse_lines;
+use eyre::{ContextCompat, Report, Result};
+use std::collections::{BTreeMap, VecDeque};
 
-pub fn part_one() -> Result<isize> {
-    let input_path = problem_input_path(20, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
-}
+type File = Vec<isize>;
 
-pub fn part_two() -> Result<isize> {
-    let input_path = problem_input_path(20, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<File> {
+    let file = parse_lines(input, str::parse::<isize>)?;
+    // the grove coordinates are counted from the 0
+    let zeros = file.iter().filter(|&&value| value == 0).count();
+    if zeros != 1 {
+        return Err(Report::msg(format!(
+            "the file should hold exactly one 0, not {zeros}"
+        )));
+    }
+    Ok(file)
 }
 
-fn part_one_inner(input: &str) -> Result<isize> {
-    let original: Vec<isize> = input.trim().lines().flat_map(str::parse::<isize>).collect();
-    let (mut values, labels, zero_id) = relabel(&original);
-    mix(&mut values, &labels);
-    Ok(extract(values, labels, zero_id))
+pub fn part_one(original: &File) -> Result<isize> {
+    let (mut values, labels, zero_id) = relabel(original);
+    mix(&mut values, &labels)?;
+    extract(values, labels, zero_id)
 }
 
-fn part_two_inner(input: &str) -> Result<isize> {
-    let original: Vec<isize> = input
-        .trim()
-        .lines()
-        .flat_map(str::parse::<isize>)
-        .map(|v| v * 811589153)
-        .collect();
+pub fn part_two(original: &File) -> Result<isize> {
+    let original: File = original.iter().map(|v| v * 811589153).collect();
     let (mut values, labels, zero_id) = relabel(&original);
     for _ in 0..10 {
-        mix(&mut values, &labels);
+        mix(&mut values, &labels)?;
     }
-    Ok(extract(values, labels, zero_id))
+    extract(values, labels, zero_id)
+}
+
+fn find(values: &VecDeque<usize>, id: usize) -> Result<usize> {
+    values
+        .iter()
+        .position(|&v| v == id)
+        .wrap_err_with(|| format!("entry {id} went missing while mixing"))
 }
 
-fn extract(values: VecDeque<usize>, labels: BTreeMap<usize, isize>, zero_id: usize) -> isize {
-    let (idx, _) = values.iter().find_position(|v| **v == zero_id).unwrap();
-    labels[&values[(idx + 1000) % values.len()]]
+fn extract(
+    values: VecDeque<usize>,
+    labels: BTreeMap<usize, isize>,
+    zero_id: usize,
+) -> Result<isize> {
+    let idx = find(&values, zero_id)?;
+    Ok(labels[&values[(idx + 1000) % values.len()]]
         + labels[&values[(idx + 2000) % values.len()]]
-        + labels[&values[(idx + 3000) % values.len()]]
+        + labels[&values[(idx + 3000) % values.len()]])
 }
 
-fn mix(values: &mut VecDeque<usize>, labels: &BTreeMap<usize, isize>) {
+fn mix(values: &mut VecDeque<usize>, labels: &BTreeMap<usize, isize>) -> Result<()> {
+    // a lone number has nowhere to move to
+    if values.len() < 2 {
+        return Ok(());
+    }
     for id in 0..values.len() {
-        let (idx, _) = values.iter().find_position(|v| **v == id).unwrap();
+        let idx = find(values, id)?;
         values.remove(idx);
         let value = labels[&id];
         let new_idx = (idx as isize + value).rem_euclid(values.len() as isize) as usize;
         values.insert(new_idx, id);
     }
+    Ok(())
 }
 
-fn relabel(original: &Vec<isize>) -> (VecDeque<usize>, BTreeMap<usize, isize>, usize) {
+fn relabel(original: &[isize]) -> (VecDeque<usize>, BTreeMap<usize, isize>, usize) {
     let mut id_to_value = BTreeMap::new();
     let mut zero_id = 0;
     for (idx, value) in original.iter().enumerate() {
@@ -76,26 +80,26 @@ fn relabel(original: &Vec<isize>) -> (VecDeque<usize>, BTreeMap<usize, isize>, u
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
+
+    aoc_core::example_tests!(crate::YEAR, 20);
 
-    const TEST_INPUT: &str = r#"
-1
-2
--3
-3
--2
-0
-4
-"#;
+    #[test]
+    fn needs_a_single_zero() {
+        assert!(parse("1\n2\n").is_err());
+        assert!(parse("0\n0\n").is_err());
+        assert_eq!(part_one(&parse("0\n").unwrap()).unwrap(), 0);
+    }
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 3);
-        assert_eq!(part_one().unwrap(), 4066);
+        let file = parse(&read_input(20).unwrap()).unwrap();
+        assert_eq!(part_one(&file).unwrap(), 4066);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 1623178306);
-        assert_eq!(part_two().unwrap(), 6704537992933);
+        let file = parse(&read_input(20).unwrap()).unwrap();
+        assert_eq!(part_two(&file).unwrap(), 6704537992933);
     }
 }
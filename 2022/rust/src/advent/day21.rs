@@ -1,51 +1,41 @@
-use crate::utils::*;
-use eyre::Result;
-use std::{collections::HashMap, fs};
+use aoc_core::parse::{parse_lines, Cursor, ParseError};
+use eyre::{ContextCompat, Report, Result};
+use std::collections::HashMap;
 
-pub fn part_one() -> Result<isize> {
-    let input_path = problem_input_path(21, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_one_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn parse(input: &str) -> Result<Compute> {
+    let entries = parse_lines(input, parse_line)?.into_iter().collect();
+    Ok(Compute { entries })
 }
 
-pub fn part_two() -> Result<isize> {
-    let input_path = problem_input_path(21, Some(1));
-    let content = fs::read_to_string(input_path)?;
-    let result = part_two_inner(&content)?;
-    println!("{result}");
-    Ok(result)
+pub fn part_one(c: &Compute) -> Result<isize> {
+    c.get("root").wrap_err("root could not be computed")
 }
 
-fn part_one_inner(input: &str) -> Result<isize> {
-    let c = Compute {
-        entries: parse_input(input),
-    };
-    Ok(c.get("root").unwrap())
-}
-
-fn part_two_inner(input: &str) -> Result<isize> {
-    let mut c = Compute {
-        entries: parse_input(input),
-    };
-    let (left, right) = match c.entries.get("root").unwrap() {
+pub fn part_two(c: &Compute) -> Result<isize> {
+    let mut c = c.clone();
+    let (left, right) = match c.entries.get("root").wrap_err("missing root")? {
         Job::Plus(left, right) => (left.clone(), right.clone()),
         Job::Minus(left, right) => (left.clone(), right.clone()),
         Job::Mul(left, right) => (left.clone(), right.clone()),
         Job::Div(left, right) => (left.clone(), right.clone()),
-        _ => panic!("literal root"),
+        Job::Literal(_) => return Err(Report::msg("root should be an operation")),
     };
     c.entries.remove("humn");
     let (unknown, result) = match (c.get(&left), c.get(&right)) {
         (Some(v), None) => (right, v),
         (None, Some(v)) => (left, v),
-        _ => panic!("both depend on humn"),
+        _ => {
+            return Err(Report::msg(
+                "exactly one side of root should depend on humn",
+            ))
+        }
     };
-    Ok(c.inverse(&unknown, result).unwrap())
+    c.inverse(&unknown, result)
+        .wrap_err("humn could not be solved for")
 }
 
-struct Compute {
+#[derive(Clone)]
+pub struct Compute {
     entries: HashMap<String, Job>,
 }
 
@@ -61,6 +51,8 @@ impl Compute {
         }
     }
 
+    /// The value of humn that makes `from` evaluate to `result`, or `None` unless exactly one
+    /// side of each operation on the way depends on humn.
     fn inverse(&self, from: &str, result: isize) -> Option<isize> {
         let entry = self.entries.get(from);
         match entry {
@@ -69,35 +61,28 @@ impl Compute {
             Some(Job::Plus(left, right)) => match (self.get(left), self.get(right)) {
                 (Some(v), None) => self.inverse(right, result - v),
                 (None, Some(v)) => self.inverse(left, result - v),
-                _ => panic!("both depend on humn"),
+                _ => None,
             },
             Some(Job::Minus(left, right)) => match (self.get(left), self.get(right)) {
                 (Some(v), None) => self.inverse(right, v - result),
                 (None, Some(v)) => self.inverse(left, result + v),
-                _ => panic!("both depend on humn"),
+                _ => None,
             },
             Some(Job::Mul(left, right)) => match (self.get(left), self.get(right)) {
                 (Some(v), None) => self.inverse(right, result / v),
                 (None, Some(v)) => self.inverse(left, result / v),
-                _ => panic!("both depend on humn"),
+                _ => None,
             },
             Some(Job::Div(left, right)) => match (self.get(left), self.get(right)) {
                 (Some(v), None) => self.inverse(right, v / result),
                 (None, Some(v)) => self.inverse(left, result * v),
-                _ => panic!("both depend on humn"),
+                _ => None,
             },
         }
     }
 }
 
-fn parse_input(input: &str) -> HashMap<String, Job> {
-    let mut entries = HashMap::new();
-    for (k, v) in input.trim().lines().map(parse_line) {
-        entries.insert(k, v);
-    }
-    entries
-}
-
+#[derive(Clone)]
 enum Job {
     Literal(isize),
     Plus(String, String),
@@ -106,57 +91,57 @@ enum Job {
     Div(String, String),
 }
 
-fn parse_line(s: &str) -> (String, Job) {
-    let (name, rest) = s.split_once(':').unwrap();
-    let job = if rest.contains('+') {
-        let (left, right) = rest.split_once('+').unwrap();
-        Job::Plus(left.trim().into(), right.trim().into())
-    } else if rest.contains('-') {
-        let (left, right) = rest.split_once('-').unwrap();
-        Job::Minus(left.trim().into(), right.trim().into())
-    } else if rest.contains('*') {
-        let (left, right) = rest.split_once('*').unwrap();
-        Job::Mul(left.trim().into(), right.trim().into())
-    } else if rest.contains('/') {
-        let (left, right) = rest.split_once('/').unwrap();
-        Job::Div(left.trim().into(), right.trim().into())
-    } else {
-        Job::Literal(str::parse(rest.trim()).unwrap())
+fn parse_name(cursor: &mut Cursor) -> Result<String, ParseError> {
+    match cursor.take_while(|c| c.is_ascii_alphanumeric()) {
+        "" => Err(cursor.error("expected a monkey's name")),
+        name => Ok(name.to_owned()),
+    }
+}
+
+fn parse_line(s: &str) -> Result<(String, Job), ParseError> {
+    let mut cursor = Cursor::new(s);
+    let name = parse_name(&mut cursor)?;
+    cursor.expect(": ")?;
+    if cursor
+        .peek()
+        .is_some_and(|c| c.is_ascii_digit() || c == '-')
+    {
+        let value = cursor.number()?;
+        cursor.end()?;
+        return Ok((name, Job::Literal(value)));
+    }
+    let left = parse_name(&mut cursor)?;
+    cursor.expect(" ")?;
+    let operation = cursor.clone();
+    let job: fn(String, String) -> Job = match cursor.next_char() {
+        Some('+') => Job::Plus,
+        Some('-') => Job::Minus,
+        Some('*') => Job::Mul,
+        Some('/') => Job::Div,
+        _ => return Err(operation.error("expected one of + - * /")),
     };
-    (name.to_owned(), job)
+    cursor.expect(" ")?;
+    let right = parse_name(&mut cursor)?;
+    cursor.end()?;
+    Ok((name, job(left, right)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::read_input;
 
-    const TEST_INPUT: &str = r#"
-root: pppw + sjmn
-dbpl: 5
-cczh: sllz + lgvd
-zczc: 2
-ptdq: humn - dvpt
-dvpt: 3
-lfqf: 4
-humn: 5
-ljgn: 2
-sjmn: drzm * dbpl
-sllz: 4
-pppw: cczh / lfqf
-lgvd: ljgn * ptdq
-drzm: hmdt - zczc
-hmdt: 32
-"#;
+    aoc_core::example_tests!(crate::YEAR, 21);
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one_inner(TEST_INPUT).unwrap(), 152);
-        assert_eq!(part_one().unwrap(), 256_997_859_093_114);
+        let c = parse(&read_input(21).unwrap()).unwrap();
+        assert_eq!(part_one(&c).unwrap(), 256_997_859_093_114);
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two_inner(TEST_INPUT).unwrap(), 301);
-        assert_eq!(part_two().unwrap(), 3952288690726);
+        let c = parse(&read_input(21).unwrap()).unwrap();
+        assert_eq!(part_two(&c).unwrap(), 3952288690726);
     }
 }
@@ -0,0 +1,56 @@
+pub mod advent;
+pub mod utils;
+
+use aoc_core::{not_implemented, run};
+use eyre::Result;
+
+const YEAR: u16 = 2022;
+
+/// Solves one part of one day against its puzzle input.
+pub fn solve(day: u8, part: u8) -> Result<()> {
+    match (day, part) {
+        (1, 1) => run(YEAR, 1, advent::day01::parse, advent::day01::part_one),
+        (1, 2) => run(YEAR, 1, advent::day01::parse, advent::day01::part_two),
+        (2, 1) => run(YEAR, 2, advent::day02::parse, advent::day02::part_one),
+        (2, 2) => run(YEAR, 2, advent::day02::parse, advent::day02::part_two),
+        (3, 1) => run(YEAR, 3, advent::day03::parse, advent::day03::part_one),
+        (3, 2) => run(YEAR, 3, advent::day03::parse, advent::day03::part_two),
+        (4, 1) => run(YEAR, 4, advent::day04::parse, advent::day04::part_one),
+        (4, 2) => run(YEAR, 4, advent::day04::parse, advent::day04::part_two),
+        (5, 1) => run(YEAR, 5, advent::day05::parse, advent::day05::part_one),
+        (5, 2) => run(YEAR, 5, advent::day05::parse, advent::day05::part_two),
+        (6, 1) => run(YEAR, 6, advent::day06::parse, advent::day06::part_one),
+        (6, 2) => run(YEAR, 6, advent::day06::parse, advent::day06::part_two),
+        (7, 1) => run(YEAR, 7, advent::day07::parse, advent::day07::part_one),
+        (7, 2) => run(YEAR, 7, advent::day07::parse, advent::day07::part_two),
+        (8, 1) => run(YEAR, 8, advent::day08::parse, advent::day08::part_one),
+        (8, 2) => run(YEAR, 8, advent::day08::parse, advent::day08::part_two),
+        (9, 1) => run(YEAR, 9, advent::day09::parse, advent::day09::part_one),
+        (9, 2) => run(YEAR, 9, advent::day09::parse, advent::day09::part_two),
+        (10, 1) => run(YEAR, 10, advent::day10::parse, advent::day10::part_one),
+        (10, 2) => run(YEAR, 10, advent::day10::parse, advent::day10::part_two),
+        (11, 1) => run(YEAR, 11, advent::day11::parse, advent::day11::part_one),
+        (11, 2) => run(YEAR, 11, advent::day11::parse, advent::day11::part_two),
+        (12, 1) => run(YEAR, 12, advent::day12::parse, advent::day12::part_one),
+        (12, 2) => run(YEAR, 12, advent::day12::parse, advent::day12::part_two),
+        (13, 1) => run(YEAR, 13, advent::day13::parse, advent::day13::part_one),
+        (13, 2) => run(YEAR, 13, advent::day13::parse, advent::day13::part_two),
+        (14, 1) => run(YEAR, 14, advent::day14::parse, advent::day14::part_one),
+        (14, 2) => run(YEAR, 14, advent::day14::parse, advent::day14::part_two),
+        (15, 1) => run(YEAR, 15, advent::day15::parse, advent::day15::part_one),
+        (15, 2) => run(YEAR, 15, advent::day15::parse, advent::day15::part_two),
+        (16, 1) => run(YEAR, 16, advent::day16::parse, advent::day16::part_one),
+        (16, 2) => run(YEAR, 16, advent::day16::parse, advent::day16::part_two),
+        (17, 1) => run(YEAR, 17, advent::day17::parse, advent::day17::part_one),
+        (17, 2) => run(YEAR, 17, advent::day17::parse, advent::day17::part_two),
+        (18, 1) => run(YEAR, 18, advent::day18::parse, advent::day18::part_one),
+        (18, 2) => run(YEAR, 18, advent::day18::parse, advent::day18::part_two),
+        (19, 1) => run(YEAR, 19, advent::day19::parse, advent::day19::part_one),
+        (19, 2) => run(YEAR, 19, advent::day19::parse, advent::day19::part_two),
+        (20, 1) => run(YEAR, 20, advent::day20::parse, advent::day20::part_one),
+        (20, 2) => run(YEAR, 20, advent::day20::parse, advent::day20::part_two),
+        (21, 1) => run(YEAR, 21, advent::day21::parse, advent::day21::part_one),
+        (21, 2) => run(YEAR, 21, advent::day21::parse, advent::day21::part_two),
+        (d, p) => not_implemented(YEAR, d, p),
+    }
+}
@@ -1,36 +1,20 @@
 use eyre::Result;
 use std::collections::BTreeMap;
-use std::error::Error;
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::str::FromStr;
+use std::path::PathBuf;
+
+use crate::YEAR;
 
 pub fn problem_input_path(day: u8, part: Option<u8>) -> PathBuf {
-    let path_str = format!(
-        "../problems/day{:02}/input{}",
-        day,
-        part.as_ref().map_or(String::new(), u8::to_string)
-    );
-    path_str.into()
+    aoc_core::problem_input_path(YEAR, day, part)
 }
 
-pub fn _read_file_split_whitespace<T: std::str::FromStr>(file: &Path) -> Result<Vec<T>>
-where
-    <T as FromStr>::Err: 'static + Error + Send + Sync,
-{
-    let content = fs::read_to_string(file)?;
-    let parsed: Result<Vec<T>, _> = content.split_whitespace().map(str::parse::<T>).collect();
-    parsed.map_err(|e| e.into())
+pub fn read_input(day: u8) -> Result<String> {
+    Ok(aoc_core::read_input(YEAR, day)?)
 }
 
-pub fn _read_file_split_on<T: std::str::FromStr>(file: &Path, pattern: &str) -> Result<Vec<T>>
-where
-    <T as FromStr>::Err: 'static + Error + Send + Sync,
-{
-    let content = fs::read_to_string(file)?;
-    let content = content.trim();
-    let parsed: Result<Vec<T>, _> = content.split(pattern).map(str::parse::<T>).collect();
-    parsed.map_err(|e| e.into())
+/// The input of one of the day's examples, for tests that need more than its answers.
+pub fn read_example(day: u8, number: u32) -> Result<String> {
+    Ok(aoc_core::example::example(YEAR, day, number)?.input)
 }
 
 // #[derive(Default)]
@@ -0,0 +1,205 @@
+//! Cellular automata over a [`Grid`], stepped one generation at a time.
+//!
+//! Each step first works out every cell's next state from the grid as it was, as in the game of
+//! life. Then any cell that [fires](Rule::fires) excites its neighbors, which may fire in turn,
+//! cascading until no more do; each cell fires at most once a step. Finally every cell settles,
+//! knowing whether it fired. A rule only needs the parts it uses: without firing, it is a plain
+//! synchronous automaton.
+
+use crate::grid::{Grid, Position};
+
+/// How the cells of an automaton change.
+pub trait Rule {
+    type Cell: Clone;
+
+    /// The cell at `position`'s state at the start of the next step, from the grid as it is.
+    fn next(&self, grid: &Grid<Self::Cell>, position: Position) -> Self::Cell;
+
+    /// Whether a cell fires, exciting its neighbors.
+    fn fires(&self, _cell: &Self::Cell) -> bool {
+        false
+    }
+
+    /// Changes a cell when one of its neighbors fires.
+    fn excite(&self, _cell: &mut Self::Cell) {}
+
+    /// Changes a cell once the cascade is over.
+    fn settle(&self, _cell: &mut Self::Cell, _fired: bool) {}
+
+    /// The cells a firing cell excites.
+    fn neighbors(&self, grid: &Grid<Self::Cell>, position: Position) -> Vec<Position> {
+        grid.neighbors8(position).collect()
+    }
+}
+
+/// What happened in one step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// Which step this was, counting from 1.
+    pub number: usize,
+    /// How many cells fired.
+    pub fired: usize,
+    /// Whether every cell fired.
+    pub synchronized: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<R: Rule> {
+    rule: R,
+    grid: Grid<R::Cell>,
+    steps: usize,
+}
+
+impl<R: Rule> Automaton<R> {
+    pub fn new(rule: R, grid: Grid<R::Cell>) -> Self {
+        Automaton {
+            rule,
+            grid,
+            steps: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<R::Cell> {
+        &self.grid
+    }
+
+    /// How many steps have been taken.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn step(&mut self) -> Step {
+        let next = self
+            .grid
+            .positions()
+            .map(|position| self.rule.next(&self.grid, position))
+            .collect();
+        self.grid = Grid::new(self.grid.rows(), self.grid.columns(), next)
+            .expect("the next generation is the same size");
+
+        let mut fired = Grid::filled(self.grid.rows(), self.grid.columns(), false);
+        let mut firing: Vec<Position> = self
+            .grid
+            .iter()
+            .filter(|(_, cell)| self.rule.fires(cell))
+            .map(|(position, _)| position)
+            .collect();
+        while let Some(position) = firing.pop() {
+            if fired[position] {
+                continue;
+            }
+            fired[position] = true;
+            for neighbor in self.rule.neighbors(&self.grid, position) {
+                self.rule.excite(&mut self.grid[neighbor]);
+                if !fired[neighbor] && self.rule.fires(&self.grid[neighbor]) {
+                    firing.push(neighbor);
+                }
+            }
+        }
+
+        for (cell, &fired) in self.grid.values_mut().zip(fired.values()) {
+            self.rule.settle(cell, fired);
+        }
+        self.steps += 1;
+        let count = fired.values().filter(|&&fired| fired).count();
+        Step {
+            number: self.steps,
+            fired: count,
+            synchronized: count == self.grid.rows() * self.grid.columns(),
+        }
+    }
+
+    /// Takes `steps` steps, calling `on_step` after each.
+    pub fn run(&mut self, steps: usize, mut on_step: impl FnMut(&Self, &Step)) {
+        for _ in 0..steps {
+            let step = self.step();
+            on_step(self, &step);
+        }
+    }
+
+    /// Steps until every cell fires in the same step, returning that step's number, or `None`
+    /// if that has not happened after `limit` more steps.
+    pub fn run_until_synchronized(&mut self, limit: usize) -> Option<usize> {
+        (0..limit)
+            .map(|_| self.step())
+            .find(|step| step.synchronized)
+            .map(|step| step.number)
+    }
+
+    /// The grid as text, one row per line, drawing each cell with `render`.
+    pub fn frame(&self, render: impl Fn(&R::Cell) -> char) -> String {
+        self.grid.display(render).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The game of life, which never fires.
+    struct Life;
+
+    impl Rule for Life {
+        type Cell = bool;
+
+        fn next(&self, grid: &Grid<bool>, position: Position) -> bool {
+            let alive = grid.neighbors8(position).filter(|&n| grid[n]).count();
+            alive == 3 || (grid[position] && alive == 2)
+        }
+    }
+
+    /// Cells that count up and, on reaching 3, fire into their neighbors and start again.
+    struct Chain;
+
+    impl Rule for Chain {
+        type Cell = u8;
+
+        fn next(&self, grid: &Grid<u8>, position: Position) -> u8 {
+            grid[position] + 1
+        }
+
+        fn fires(&self, cell: &u8) -> bool {
+            *cell >= 3
+        }
+
+        fn excite(&self, cell: &mut u8) {
+            *cell += 1;
+        }
+
+        fn settle(&self, cell: &mut u8, fired: bool) {
+            if fired {
+                *cell = 0;
+            }
+        }
+
+        fn neighbors(&self, grid: &Grid<u8>, position: Position) -> Vec<Position> {
+            grid.neighbors4(position).collect()
+        }
+    }
+
+    #[test]
+    fn blinks() {
+        let grid = Grid::parse(".....\n..#..\n..#..\n..#..\n.....", |c| Ok(c == '#')).unwrap();
+        let mut life = Automaton::new(Life, grid);
+        let mut frames = Vec::new();
+        life.run(2, |life, step| {
+            assert_eq!(step.fired, 0);
+            frames.push(life.frame(|&alive| if alive { '#' } else { '.' }));
+        });
+        assert_eq!(frames[0], ".....\n.....\n.###.\n.....\n.....\n");
+        assert_eq!(frames[1], ".....\n..#..\n..#..\n..#..\n.....\n");
+        assert_eq!(life.steps(), 2);
+    }
+
+    #[test]
+    fn cascades_and_synchronizes() {
+        let grid = Grid::new(1, 4, vec![2, 1, 1, 1]).unwrap();
+        let mut chain = Automaton::new(Chain, grid);
+        // only the first cell reaches 3 by itself, but each sets off the next
+        let step = chain.step();
+        assert_eq!((step.fired, step.synchronized), (4, true));
+        assert_eq!(chain.frame(|&n| char::from(b'0' + n)), "0000\n");
+        // then they all count up together
+        assert_eq!(chain.run_until_synchronized(10), Some(4));
+    }
+}
@@ -0,0 +1,160 @@
+//! Finding where a long simulation starts repeating itself, so that it can be skipped ahead.
+//!
+//! [`floyd`] and [`brent`] only keep two states around and compare them directly, which suits
+//! small states. [`find_cycle`] instead remembers a key for every state it has seen, which
+//! suits large states that only need part of themselves compared, and records a metric at
+//! every step to [`Cycle::extrapolate`] from.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence of states that, from step `start` on, repeats every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as the state at `step`.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.period
+        }
+    }
+
+    /// The value at step `n` of a metric that grows by the same amount every period, given
+    /// its value at every step until at least one period in. `history[i]` is the metric after
+    /// `i` steps.
+    pub fn extrapolate(&self, history: &[i64], n: usize) -> Option<i64> {
+        if let Some(&value) = history.get(n) {
+            return Some(value);
+        }
+        let growth = history.get(self.start + self.period)? - history.get(self.start)?;
+        let periods = ((n - self.start) / self.period) as i64;
+        Some(history.get(self.equivalent_step(n))? + growth * periods)
+    }
+}
+
+/// Floyd's tortoise and hare: steps one state once and another twice until they meet.
+pub fn floyd<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+    // the hare is now a multiple of the period ahead, so walking the tortoise from the start
+    // alongside it makes them meet where the cycle starts
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { start, period }
+}
+
+/// Brent's algorithm: finds the period first, by teleporting one state to the other at every
+/// power of two, which takes fewer steps than [`floyd`].
+pub fn brent<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+    // with the hare a period ahead, they first meet where the cycle starts
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+/// Steps `state` in place until the `key` of a state repeats, recording `metric` after every
+/// step, including before the first. Two states with the same key must behave the same from
+/// then on.
+pub fn find_cycle<S, K: Hash + Eq>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> i64,
+) -> (Cycle, Vec<i64>) {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    loop {
+        let steps = history.len();
+        history.push(metric(state));
+        if let Some(start) = seen.insert(key(state), steps) {
+            let cycle = Cycle {
+                start,
+                period: steps - start,
+            };
+            return (cycle, history);
+        }
+        step(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3, 10, 101, 2, 5, 26, 167, 95, 101, ... cycles from 101
+    fn next(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let expected = Cycle {
+            start: 2,
+            period: 6,
+        };
+        assert_eq!(floyd(3, next), expected);
+        assert_eq!(brent(3, next), expected);
+        let (cycle, history) = find_cycle(&mut 3, |x| *x = next(x), |&x| x, |&x| x as i64);
+        assert_eq!(cycle, expected);
+        assert_eq!(history.len(), 9);
+    }
+
+    #[test]
+    fn extrapolates_linear_growth() {
+        // a counter that grows by 5 over each repeat of a 3 step cycle entered after 2 steps
+        let history = [0, 1, 3, 4, 6, 8, 9, 11];
+        let cycle = Cycle {
+            start: 2,
+            period: 3,
+        };
+        assert_eq!(cycle.equivalent_step(1), 1);
+        assert_eq!(cycle.equivalent_step(9), 3);
+        assert_eq!(cycle.extrapolate(&history, 7), Some(11));
+        assert_eq!(cycle.extrapolate(&history, 9), Some(4 + 10));
+        assert_eq!(cycle.extrapolate(&history[..4], 9), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Differential testing: checking that the different ways of solving a day agree.
+//!
+//! Some days have a fast solver and a slow one that is easier to trust, or one strategy that is
+//! meant to give the same answer as another. [`check_agreement`] generates random inputs with a
+//! proptest [`Strategy`], solves each with every solver, and fails if any of them disagree.
+//!
+//! A failing input is shrunk to the smallest one proptest can find that still disagrees, then
+//! saved as `problems/dayNN/counterexampleN` so it can be run and debugged like any other input.
+//! Saved counterexamples are checked before any random inputs on every later run, and once the
+//! solvers agree on one it can be renamed to an `exampleN` with its answers, or deleted.
+
+use eyre::{Result, WrapErr};
+use proptest::collection::vec;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+use std::fmt::{Debug, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::ops::{Range, RangeInclusive};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use crate::problem_dir;
+
+/// A solver with its answer already written out, so that answers of any type can be compared.
+type Solve<'a> = Box<dyn Fn(&str) -> Result<String, String> + 'a>;
+
+/// One way of solving a part, with a name to tell it apart in failures.
+pub struct Solver<'a> {
+    name: &'a str,
+    solve: Solve<'a>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new<A: Display, E: Debug>(
+        name: &'a str,
+        solve: impl Fn(&str) -> Result<A, E> + 'a,
+    ) -> Self {
+        Solver {
+            name,
+            solve: Box::new(move |input| {
+                solve(input)
+                    .map(|answer| answer.to_string().trim().to_string())
+                    .map_err(|error| format!("{error:?}"))
+            }),
+        }
+    }
+
+    fn answer(&self, input: &str) -> Result<String, String> {
+        catch_unwind(AssertUnwindSafe(|| (self.solve)(input)))
+            .unwrap_or_else(|_| Err("panicked".to_string()))
+    }
+}
+
+/// Solves `input` with every solver, describing their answers if they do not all give the same
+/// one. A solver that fails or panics never agrees.
+fn disagreement(input: &str, solvers: &[Solver]) -> Option<String> {
+    let answers: Vec<_> = solvers.iter().map(|solver| solver.answer(input)).collect();
+    if answers
+        .iter()
+        .all(|answer| answer.is_ok() && answer == &answers[0])
+    {
+        return None;
+    }
+    let lines: Vec<_> = solvers
+        .iter()
+        .zip(answers)
+        .map(|(solver, answer)| match answer {
+            Ok(answer) => format!("{}: {answer}", solver.name),
+            Err(error) => format!("{} failed: {error}", solver.name),
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// The counterexamples saved for a day, in order of their numbers.
+pub fn counterexamples(year: u16, day: u8) -> Vec<PathBuf> {
+    let mut numbered: Vec<_> = fs::read_dir(problem_dir(year, day))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let number = name.strip_prefix("counterexample")?.parse::<u32>().ok()?;
+            Some((number, path))
+        })
+        .collect();
+    numbered.sort();
+    numbered.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Saves `input` under the first counterexample number not already taken.
+fn save_counterexample(year: u16, day: u8, input: &str) -> Result<PathBuf> {
+    let dir = problem_dir(year, day);
+    for number in 1.. {
+        let path = dir.join(format!("counterexample{number}"));
+        let file = OpenOptions::new().write(true).create_new(true).open(&path);
+        match file {
+            Ok(mut file) => {
+                file.write_all(input.as_bytes())
+                    .wrap_err_with(|| format!("could not write {}", path.display()))?;
+                return Ok(path);
+            }
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => continue,
+            Err(error) => {
+                return Err(error).wrap_err_with(|| format!("could not create {}", path.display()))
+            }
+        }
+    }
+    unreachable!("ran out of counterexample numbers")
+}
+
+/// Checks that every solver gives the same answer, first for each saved counterexample for the
+/// day and then for `cases` inputs from `inputs`. Panics on the first disagreement, saving the
+/// shrunk input as a new counterexample and naming its path.
+pub fn check_agreement<S: Strategy<Value = String>>(
+    year: u16,
+    day: u8,
+    cases: u32,
+    inputs: S,
+    solvers: &[Solver],
+) {
+    assert!(solvers.len() >= 2, "need at least two solvers to compare");
+    for path in counterexamples(year, day) {
+        let input = fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("could not read {}: {error}", path.display()));
+        if let Some(answers) = disagreement(&input, solvers) {
+            panic!("solvers still disagree on {}:\n{answers}", path.display());
+        }
+    }
+
+    let config = Config {
+        cases,
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let result =
+        TestRunner::new(config).run(&inputs, |input| match disagreement(&input, solvers) {
+            Some(answers) => Err(TestCaseError::fail(answers)),
+            None => Ok(()),
+        });
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(answers, input)) => {
+            let path =
+                save_counterexample(year, day, &input).unwrap_or_else(|error| panic!("{error:?}"));
+            panic!("solvers disagree on {}:\n{answers}", path.display());
+        }
+        Err(TestError::Abort(reason)) => panic!("could not generate inputs: {reason}"),
+    }
+}
+
+/// Rectangular grids of digits, one row per line, as many puzzles give their maps.
+pub fn digit_grid(
+    rows: Range<usize>,
+    columns: Range<usize>,
+    digits: RangeInclusive<u8>,
+) -> impl Strategy<Value = String> {
+    (rows, columns)
+        .prop_flat_map(move |(rows, columns)| vec(vec(digits.clone(), columns), rows))
+        .prop_map(|grid| {
+            grid.iter()
+                .map(|row| row.iter().map(u8::to_string).collect::<String>() + "\n")
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit_sum(input: &str) -> Result<u32> {
+        Ok(input.chars().filter_map(|c| c.to_digit(10)).sum())
+    }
+
+    #[test]
+    fn compares_every_solver() {
+        let solvers = [
+            Solver::new("sum", digit_sum),
+            Solver::new("sum by line", |input: &str| {
+                input.lines().map(digit_sum).sum::<Result<u32>>()
+            }),
+        ];
+        // there is no problems directory for year 0, so a failure here saves nothing
+        check_agreement(0, 0, 32, digit_grid(1..5, 1..5, 0..=9), &solvers);
+
+        let wrong = Solver::new("count", |input: &str| {
+            Ok::<_, String>(input.chars().filter(char::is_ascii_digit).count())
+        });
+        let solvers = [Solver::new("sum", digit_sum), wrong];
+        assert_eq!(disagreement("11\n11\n", &solvers), None);
+        assert_eq!(
+            disagreement("12\n", &solvers).as_deref(),
+            Some("sum: 3\ncount: 2")
+        );
+    }
+}
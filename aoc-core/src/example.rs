@@ -0,0 +1,198 @@
+//! Worked examples from puzzle statements, kept as files next to the puzzle input.
+//!
+//! Each example is a file `problems/dayNN/exampleN` holding the example input, with a file
+//! `exampleN.answers` beside it holding the answers the statement gives for it:
+//!
+//! ```text
+//! part_one: 3068
+//! part_two: 1514285714288
+//! ```
+//!
+//! A part may be left out if the statement has no answer for it, and the whole file if it has
+//! none. An answer that spans several lines, such as a rendered image, goes on the lines after
+//! an empty `part_two:`.
+//!
+//! [`example_tests!`](crate::example_tests) turns every example for a day into tests, so adding
+//! an example is a matter of dropping its files in.
+
+use eyre::{Report, Result, WrapErr};
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::problem_dir;
+
+/// One example input, along with the answers it should give.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub path: PathBuf,
+    pub input: String,
+    answers: BTreeMap<u8, String>,
+}
+
+impl Example {
+    /// The expected answer for `part`, if the statement gives one.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers.get(&part).map(String::as_str)
+    }
+}
+
+/// Every example for a day, in order of their numbers.
+pub fn examples(year: u16, day: u8) -> Result<Vec<Example>> {
+    let dir = problem_dir(year, day);
+    let mut numbered = Vec::new();
+    for entry in fs::read_dir(&dir).wrap_err_with(|| format!("could not read {}", dir.display()))? {
+        let path = entry?.path();
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("example")?.parse::<u32>().ok());
+        if let Some(number) = number {
+            numbered.push((number, path));
+        }
+    }
+    numbered.sort();
+    numbered.into_iter().map(|(_, path)| load(path)).collect()
+}
+
+/// The example with the given number for a day.
+pub fn example(year: u16, day: u8, number: u32) -> Result<Example> {
+    load(problem_dir(year, day).join(format!("example{number}")))
+}
+
+fn load(path: PathBuf) -> Result<Example> {
+    let input =
+        fs::read_to_string(&path).wrap_err_with(|| format!("could not read {}", path.display()))?;
+    let answers_path = path.with_extension("answers");
+    // an example the statement gives no answers for may have no answers file at all
+    let answers = match fs::read_to_string(&answers_path) {
+        Ok(answers) => answers,
+        Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+        Err(error) => {
+            return Err(error)
+                .wrap_err_with(|| format!("could not read {}", answers_path.display()))
+        }
+    };
+    let answers = parse_answers(&answers)
+        .wrap_err_with(|| format!("could not parse {}", answers_path.display()))?;
+    Ok(Example {
+        path,
+        input,
+        answers,
+    })
+}
+
+fn parse_answers(text: &str) -> Result<BTreeMap<u8, String>> {
+    let mut answers = BTreeMap::new();
+    let mut lines = text.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (key, answer) = line
+            .split_once(':')
+            .ok_or_else(|| Report::msg(format!("expected `part_one: answer`, found {line:?}")))?;
+        let part = match key.trim() {
+            "part_one" => 1,
+            "part_two" => 2,
+            other => return Err(Report::msg(format!("unknown part {other:?}"))),
+        };
+        let answer = if answer.trim().is_empty() {
+            let mut block = Vec::new();
+            while let Some(line) = lines.next_if(|line| !line.starts_with("part_")) {
+                block.push(line);
+            }
+            block.join("\n").trim().to_string()
+        } else {
+            answer.trim().to_string()
+        };
+        answers.insert(part, answer);
+    }
+    Ok(answers)
+}
+
+/// Solves `part` of every example for a day, panicking with the example's path if any answer
+/// differs from the expected one. Examples without an answer for `part` are skipped, but a day
+/// with no examples at all is a failure, so that a missing directory is not mistaken for a pass.
+pub fn check_examples<A: Display, E: Debug>(
+    year: u16,
+    day: u8,
+    part: u8,
+    solve: impl Fn(&str) -> std::result::Result<A, E>,
+) {
+    let examples = examples(year, day).unwrap_or_else(|error| panic!("{error:?}"));
+    assert!(
+        !examples.is_empty(),
+        "no examples in {}",
+        problem_dir(year, day).display()
+    );
+    for example in examples {
+        let Some(expected) = example.answer(part) else {
+            continue;
+        };
+        let answer = solve(&example.input).unwrap_or_else(|error| {
+            panic!("{} failed part {part}: {error:?}", example.path.display())
+        });
+        assert_eq!(
+            answer.to_string().trim(),
+            expected,
+            "wrong answer to part {part} of {}",
+            example.path.display()
+        );
+    }
+}
+
+/// Generates an `examples` module of `part_one_examples` and `part_two_examples` tests that check
+/// every example for a day against its answers. By default they solve with the `parse`,
+/// `part_one` and `part_two` in scope, but other functions can be given, e.g. for examples that
+/// use smaller constants.
+#[macro_export]
+macro_rules! example_tests {
+    ($year:expr, $day:expr) => {
+        $crate::example_tests!($year, $day, parse, part_one, part_two);
+    };
+    ($year:expr, $day:expr, $parse:expr, $part_one:expr, $part_two:expr) => {
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn part_one_examples() {
+                $crate::example::check_examples($year, $day, 1, |input| $part_one(&$parse(input)?));
+            }
+
+            #[test]
+            fn part_two_examples() {
+                $crate::example::check_examples($year, $day, 2, |input| $part_two(&$parse(input)?));
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse_answers("part_one: 24000\n\npart_two:\n#..#\n.##.\n").unwrap();
+        assert_eq!(answers.get(&1).map(String::as_str), Some("24000"));
+        assert_eq!(answers.get(&2).map(String::as_str), Some("#..#\n.##."));
+        assert!(parse_answers("part_three: 1").is_err());
+        assert!(parse_answers("24000").is_err());
+    }
+
+    #[test]
+    fn loads_examples_without_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("example1");
+        fs::write(&path, "1\n2\n").unwrap();
+        let example = load(path).unwrap();
+        assert_eq!(example.input, "1\n2\n");
+        assert_eq!((example.answer(1), example.answer(2)), (None, None));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
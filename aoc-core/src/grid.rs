@@ -0,0 +1,351 @@
+//! A dense, rectangular grid addressed by `(row, column)`.
+
+use eyre::{Report, Result, WrapErr};
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position in a [`Grid`].
+pub type Position = (usize, usize);
+
+/// A `(row, column)` step between positions, e.g. `(-1, 0)` for up.
+pub type Offset = (isize, isize);
+
+/// Up, right, down and left.
+pub const CARDINALS: [Offset; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The cardinals and diagonals, clockwise from up.
+pub const ADJACENT: [Offset; 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    columns: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    pub fn new(rows: usize, columns: usize, cells: Vec<T>) -> Result<Self> {
+        if rows * columns != cells.len() {
+            return Err(Report::msg(format!(
+                "{} cells do not fill a {rows}x{columns} grid",
+                cells.len()
+            )));
+        }
+        Ok(Grid {
+            cells,
+            rows,
+            columns,
+        })
+    }
+
+    pub fn filled(rows: usize, columns: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; rows * columns],
+            rows,
+            columns,
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let columns = rows.first().map_or(0, Vec::len);
+        let row_count = rows.len();
+        let mut cells = Vec::with_capacity(row_count * columns);
+        for (index, row) in rows.into_iter().enumerate() {
+            if row.len() != columns {
+                return Err(Report::msg(format!(
+                    "row {index} has {} cells, expected {columns}",
+                    row.len()
+                )));
+            }
+            cells.extend(row);
+        }
+        Grid::new(row_count, columns, cells)
+    }
+
+    /// Parses one row per line, converting each character with `cell`.
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let rows = text
+            .trim()
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.trim()
+                    .chars()
+                    .enumerate()
+                    .map(|(column, c)| {
+                        cell(c).wrap_err_with(|| {
+                            format!("bad cell {c:?} at line {}, column {}", row + 1, column + 1)
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_>>()?;
+        Grid::from_rows(rows)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.rows && column < self.columns
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.columns + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.columns + position.1])
+        } else {
+            None
+        }
+    }
+
+    /// The position one `offset` away, if it is still inside the grid.
+    pub fn step(&self, (row, column): Position, (dr, dc): Offset) -> Option<Position> {
+        let position = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Every position, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |row| (0..columns).map(move |column| (row, column)))
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn column(&self, column: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            column < self.columns,
+            "column {column} is outside of a {}x{} grid",
+            self.rows,
+            self.columns
+        );
+        self.cells[column..].iter().step_by(self.columns.max(1))
+    }
+
+    pub fn row_slices(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on zero, and an empty grid has no rows anyway
+        self.cells.chunks(self.columns.max(1))
+    }
+
+    /// The in-bounds positions up, right, down and left of `position`.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        CARDINALS
+            .into_iter()
+            .filter_map(move |offset| self.step(position, offset))
+    }
+
+    /// The in-bounds positions surrounding `position`, diagonals included.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |offset| self.step(position, offset))
+    }
+
+    /// The positions from `position` (exclusive) towards the edge, one `offset` at a time.
+    pub fn ray(&self, position: Position, offset: Offset) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.step(position, offset), move |&next| {
+            self.step(next, offset)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+
+    fn rebuild(&self, rows: usize, columns: usize, source: impl Fn(Position) -> Position) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+        Grid {
+            cells,
+            rows,
+            columns,
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.columns, self.rows, |(row, column)| (column, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.rebuild(self.columns, self.rows, |(row, column)| {
+            (rows - 1 - column, row)
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let columns = self.columns;
+        self.rebuild(self.columns, self.rows, |(row, column)| {
+            (column, columns - 1 - row)
+        })
+    }
+
+    /// Displays the grid one row per line, drawing each cell with `to_char`.
+    pub fn display<F: Fn(&T) -> char>(&self, to_char: F) -> GridDisplay<'_, T, F> {
+        GridDisplay {
+            grid: self,
+            to_char,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside of a {}x{} grid",
+                self.rows, self.columns
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (rows, columns) = (self.rows, self.columns);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside of a {rows}x{columns} grid"))
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    to_char: F,
+}
+
+impl<T, F: Fn(&T) -> char> Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.row_slices() {
+            let line: String = row.iter().map(&self.to_char).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(|&c| c).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(text: &str) -> Grid<u32> {
+        Grid::parse(text, |c| {
+            c.to_digit(10).ok_or_else(|| Report::msg("not a digit"))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_and_indexes() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.rows(), grid.columns()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        let error = Grid::parse("12\n3x", |c| {
+            c.to_digit(10).ok_or_else(|| Report::msg("not a digit"))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "bad cell 'x' at line 2, column 2");
+        assert!(Grid::parse("12\n3", Ok).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of a 2x3 grid")]
+    fn columns_stay_in_bounds() {
+        let _ = digits("123\n456\n").column(3);
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = digits("123\n456\n789");
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.ray((2, 0), (-1, 1)).collect::<Vec<_>>(),
+            [(1, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = digits("123\n456");
+        let render = |grid: &Grid<u32>| {
+            grid.display(|&d| char::from_digit(d, 10).unwrap())
+                .to_string()
+        };
+        assert_eq!(render(&grid.transpose()), "14\n25\n36\n");
+        assert_eq!(render(&grid.rotate_clockwise()), "41\n52\n63\n");
+        assert_eq!(render(&grid.rotate_counterclockwise()), "36\n25\n14\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,87 @@
+pub mod automaton;
+pub mod cycle;
+#[cfg(feature = "differential")]
+pub mod differential;
+pub mod example;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod region;
+pub mod search;
+pub mod sparse_grid;
+pub mod statement;
+
+use eyre::{Report, Result, WrapErr};
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Instant;
+
+/// The workspace root, which holds one directory per year.
+pub fn workspace_root() -> &'static Path {
+    let core = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // aoc-core lives directly under the workspace root
+    core.parent().unwrap_or(core)
+}
+
+pub fn problem_dir(year: u16, day: u8) -> PathBuf {
+    workspace_root()
+        .join(year.to_string())
+        .join("problems")
+        .join(format!("day{:02}", day))
+}
+
+pub fn problem_input_path(year: u16, day: u8, part: Option<u8>) -> PathBuf {
+    problem_dir(year, day).join(format!(
+        "input{}",
+        part.as_ref().map_or(String::new(), u8::to_string)
+    ))
+}
+
+pub fn read_input(year: u16, day: u8) -> std::io::Result<String> {
+    fs::read_to_string(problem_input_path(year, day, Some(1)))
+}
+
+pub fn split_whitespace<T: FromStr, E: From<T::Err>>(input: &str) -> Result<Vec<T>, E> {
+    let parsed: Result<Vec<T>, _> = input.split_whitespace().map(str::parse::<T>).collect();
+    parsed.map_err(E::from)
+}
+
+pub fn split_on<T: FromStr, E: From<T::Err>>(input: &str, pattern: &str) -> Result<Vec<T>, E> {
+    let parsed: Result<Vec<T>, _> = input.trim().split(pattern).map(str::parse::<T>).collect();
+    parsed.map_err(E::from)
+}
+
+/// Reads the day's input, then parses and solves it, timing each phase separately. A failure to
+/// parse is reported with the day and the file it was read from.
+pub fn run<I, A: Display, E>(
+    year: u16,
+    day: u8,
+    parse: impl Fn(&str) -> Result<I, E>,
+    solve: impl Fn(&I) -> Result<A, E>,
+) -> Result<()>
+where
+    Report: From<E>,
+{
+    let path = problem_input_path(year, day, Some(1));
+    let content =
+        fs::read_to_string(&path).wrap_err_with(|| format!("could not read {}", path.display()))?;
+    let start = Instant::now();
+    let input = parse(&content)
+        .map_err(Report::from)
+        .wrap_err_with(|| format!("{year} day {day}: could not parse {}", path.display()))?;
+    let parsed = start.elapsed();
+    let start = Instant::now();
+    let answer = solve(&input)?;
+    let solved = start.elapsed();
+    println!("{answer}");
+    println!("Parse: {parsed:?}, Solve: {solved:?}");
+    Ok(())
+}
+
+pub fn not_implemented(year: u16, day: u8, part: u8) -> Result<()> {
+    Err(Report::msg(format!(
+        "{year} day {day}, part {part} is not yet implemented"
+    )))
+}
@@ -0,0 +1,497 @@
+//! Small parsing helpers that report where in the input they failed.
+//!
+//! Lines with a fixed shape are matched against a template with [`scan!`](crate::scan):
+//!
+//! ```
+//! let (amount, from, to) = aoc_core::scan!("move 3 from 1 to 2", "move {n} from {a} to {b}" => usize, usize, usize)?;
+//! assert_eq!((amount, from, to), (3, 1, 2));
+//! # Ok::<(), aoc_core::parse::ParseError>(())
+//! ```
+//!
+//! Nested formats are walked with a [`Cursor`] instead.
+
+use std::char::ParseCharError;
+use std::convert::Infallible;
+use std::fmt::{self, Display};
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::FromStr;
+
+use eyre::Report;
+
+/// A parse failure, pointing at the line and column it happened at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counted from one.
+    pub line: usize,
+    /// Counted in characters from one.
+    pub column: usize,
+    /// The whole line the failure happened on.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at the byte `offset` of `input`.
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = input[line_start..]
+            .find('\n')
+            .map_or(input.len(), |newline| line_start + newline);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..line_end].to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error found in a single line to where that line is in the whole input.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error from parsing one line, which can be pointed at that line.
+pub trait LineError {
+    /// Where in `line` this went wrong. Errors that don't know point at the start of the line.
+    fn locate(self, line: &str) -> ParseError;
+}
+
+impl LineError for ParseError {
+    fn locate(self, _: &str) -> ParseError {
+        self
+    }
+}
+
+impl LineError for Report {
+    fn locate(self, line: &str) -> ParseError {
+        self.downcast::<ParseError>()
+            .unwrap_or_else(|report| ParseError::at(line, 0, format!("{report:#}")))
+    }
+}
+
+macro_rules! impl_line_error {
+    ($($error:ty),+) => {$(
+        impl LineError for $error {
+            fn locate(self, line: &str) -> ParseError {
+                ParseError::at(line, 0, self.to_string())
+            }
+        }
+    )+};
+}
+
+impl_line_error!(ParseIntError, ParseFloatError, ParseCharError);
+
+impl LineError for Infallible {
+    fn locate(self, _: &str) -> ParseError {
+        match self {}
+    }
+}
+
+/// Parses every non-empty line of `input` with `parse`, numbering any error by its line.
+pub fn parse_lines<T, E: LineError>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse(line).map_err(|error| error.locate(line).on_line(index + 1)))
+        .collect()
+}
+
+/// Parses every block of lines in `input`, where blocks are separated by blank lines. Errors
+/// from a block are counted from its first line, and numbered by where that is in `input`.
+pub fn parse_blocks<T, E: LineError>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let lines: Vec<&str> = input.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        match (start, line.trim().is_empty()) {
+            (None, false) => start = Some(index),
+            (Some(first), true) => {
+                blocks.push((first, &lines[first..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(first) = start {
+        blocks.push((first, &lines[first..]));
+    }
+    blocks
+        .into_iter()
+        .map(|(first, block)| {
+            let block = block.join("\n");
+            parse(&block).map_err(|error| error.locate(&block).on_line(first + 1))
+        })
+        .collect()
+}
+
+/// Parses each field of `input` split on `separator`, such as a comma-separated list of numbers.
+/// Whitespace around the fields is ignored, and an error points at the field that failed.
+pub fn parse_separated<T: FromStr>(input: &str, separator: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    let mut offset = 0;
+    input
+        .split(separator)
+        .map(|field| {
+            let start = offset + (field.len() - field.trim_start().len());
+            offset += field.len() + separator.len();
+            let field = field.trim();
+            field.parse().map_err(|error| {
+                ParseError::at(input, start, format!("could not parse {field:?}: {error}"))
+            })
+        })
+        .collect()
+}
+
+/// A position in some input, for recursive descent parsers.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Cursor { input, offset: 0 }
+    }
+
+    /// Everything not yet consumed.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consumes `literal` if the input continues with it.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        let matches = self.rest().starts_with(literal);
+        if matches {
+            self.offset += literal.len();
+        }
+        matches
+    }
+
+    /// Consumes `literal`, or fails if the input does not continue with it.
+    pub fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {literal:?}")))
+        }
+    }
+
+    pub fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.offset += length;
+        &rest[..length]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes an integer, with an optional leading minus sign.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.offset;
+        self.eat("-");
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let number = &self.input[start..self.offset];
+        if digits.is_empty() {
+            self.offset = start;
+            return Err(self.error("expected a number"));
+        }
+        number
+            .parse()
+            .map_err(|_| ParseError::at(self.input, start, format!("{number} is out of range")))
+    }
+
+    /// Fails unless all of the input has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("expected the end of the input"))
+        }
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, self.offset, message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Literal(String),
+    Field(String),
+}
+
+/// A line template like `move {n} from {a} to {b}`, where each `{}` matches anything up to the
+/// literal text that follows it. Field names are only for readability.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    template: String,
+    pieces: Vec<Piece>,
+}
+
+impl Pattern {
+    /// Panics if two fields are next to each other, since there is no telling where the first
+    /// ends, or if a brace is never closed. Templates are written in the source, so either is
+    /// a bug rather than bad input.
+    pub fn new(template: &str) -> Self {
+        let mut pieces = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                pieces.push(Piece::Literal(rest[..open].to_string()));
+            } else if matches!(pieces.last(), Some(Piece::Field(_))) {
+                panic!("adjacent fields in pattern {template:?}");
+            }
+            let close = rest[open..]
+                .find('}')
+                .unwrap_or_else(|| panic!("unclosed field in pattern {template:?}"));
+            pieces.push(Piece::Field(rest[open + 1..open + close].to_string()));
+            rest = &rest[open + close + 1..];
+        }
+        if !rest.is_empty() {
+            pieces.push(Piece::Literal(rest.to_string()));
+        }
+        Pattern {
+            template: template.to_string(),
+            pieces,
+        }
+    }
+
+    /// Matches `line` against the template, giving the text of each field in order.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Captures<'a>, ParseError> {
+        let mut cursor = Cursor::new(line);
+        let mut fields = Vec::new();
+        let mut pieces = self.pieces.iter().peekable();
+        while let Some(piece) = pieces.next() {
+            match piece {
+                Piece::Literal(literal) => cursor.expect(literal)?,
+                Piece::Field(name) => {
+                    let start = cursor.offset;
+                    let length = match pieces.peek() {
+                        Some(Piece::Literal(next)) => {
+                            cursor.rest().find(next.as_str()).ok_or_else(|| {
+                                cursor.error(format!("expected {next:?} after {{{name}}}"))
+                            })?
+                        }
+                        _ => cursor.rest().len(),
+                    };
+                    cursor.offset += length;
+                    fields.push(Field {
+                        name: name.clone(),
+                        start,
+                        text: &line[start..start + length],
+                    });
+                }
+            }
+        }
+        cursor.end().map_err(|error| ParseError {
+            message: format!("{} for {:?}", error.message, self.template),
+            ..error
+        })?;
+        Ok(Captures {
+            line,
+            fields: fields.into_iter(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Field<'a> {
+    name: String,
+    start: usize,
+    text: &'a str,
+}
+
+/// The fields a [`Pattern`] matched, taken in order.
+#[derive(Debug, Clone)]
+pub struct Captures<'a> {
+    line: &'a str,
+    fields: std::vec::IntoIter<Field<'a>>,
+}
+
+impl<'a> Captures<'a> {
+    /// The text of the next field.
+    pub fn next_text(&mut self) -> Result<&'a str, ParseError> {
+        self.fields
+            .next()
+            .map(|field| field.text)
+            .ok_or_else(|| ParseError::at(self.line, self.line.len(), "too few fields"))
+    }
+
+    /// Parses the next field as a `T`.
+    pub fn next_parsed<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let field = self
+            .fields
+            .next()
+            .ok_or_else(|| ParseError::at(self.line, self.line.len(), "too few fields"))?;
+        field.text.trim().parse().map_err(|_| {
+            ParseError::at(
+                self.line,
+                field.start,
+                format!(
+                    "{{{}}} should be a {}, found {:?}",
+                    field.name,
+                    short_type_name::<T>(),
+                    field.text
+                ),
+            )
+        })
+    }
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Matches a line against a [`Pattern`] template and parses each field as the type given for
+/// it, returning them as a tuple. The template is compiled once per call site, the first time
+/// it is used.
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $template:literal => $($field:ty),+ $(,)?) => {{
+        static PATTERN: ::std::sync::OnceLock<$crate::parse::Pattern> =
+            ::std::sync::OnceLock::new();
+        PATTERN
+            .get_or_init(|| $crate::parse::Pattern::new($template))
+            .captures($line)
+            .and_then(|mut captures| {
+                Ok(($(captures.next_parsed::<$field>()?,)+))
+            })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scans_templates() {
+        let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+        let scanned = scan!(
+            line,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}" => i32, i32, i32, i32
+        );
+        assert_eq!(scanned, Ok((2, -18, -2, 15)));
+        let (axis, at) = scan!("fold along y=7", "fold along {axis}={at}" => char, usize).unwrap();
+        assert_eq!((axis, at), ('y', 7));
+        // the pattern compiled for the first line is reused for the rest
+        let moves: Vec<_> = ["move 1 from 2 to 3", "move 4 from 5 to 6", "move 7"]
+            .into_iter()
+            .map(|line| scan!(line, "move {n} from {a} to {b}" => u8, u8, u8).ok())
+            .collect();
+        assert_eq!(moves, vec![Some((1, 2, 3)), Some((4, 5, 6)), None]);
+    }
+
+    #[test]
+    fn points_at_failures() {
+        let error =
+            scan!("move 1 from x to 3", "move {n} from {a} to {b}" => u8, u8, u8).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 13));
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 13: {a} should be a u8, found \"x\"\n    move 1 from x to 3\n                ^"
+        );
+        let error = scan!("move 1 to 3", "move {n} from {a} to {b}" => u8, u8, u8).unwrap_err();
+        assert_eq!(error.message, "expected \" from \" after {n}");
+        let error =
+            parse_lines("1\n2\n\nx", |line| scan!(line, "{}" => u8).map(|(n,)| n)).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        let error = parse_lines("1\n-2", str::parse::<u8>).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "-2"));
+    }
+
+    #[test]
+    fn numbers_block_errors_by_input_line() {
+        let input = "\n1\n2\n\n\n3\nx\n";
+        let sums = parse_blocks("1\n2\n\n3", |block| {
+            parse_lines(block, str::parse::<u8>).map(|n| n.iter().sum::<u8>())
+        });
+        assert_eq!(sums, Ok(vec![3, 3]));
+        let error = parse_blocks(input, |block| parse_lines(block, str::parse::<u8>)).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (7, "x"));
+    }
+
+    #[test]
+    fn points_at_separated_fields() {
+        assert_eq!(parse_separated::<i32>("1, -2,3\n", ","), Ok(vec![1, -2, 3]));
+        let error = parse_separated::<u8>("3,4,3,1,x2\n", ",").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert_eq!(error.text, "3,4,3,1,x2");
+        assert_eq!(
+            error.message,
+            "could not parse \"x2\": invalid digit found in string"
+        );
+        let error = parse_separated::<u8>("1,  ,2", ",").unwrap_err();
+        assert_eq!(error.column, 5);
+    }
+
+    #[test]
+    fn cursor_walks_nested_input() {
+        fn sum(cursor: &mut Cursor) -> Result<i32, ParseError> {
+            cursor.skip_whitespace();
+            if cursor.eat("[") {
+                let mut total = 0;
+                while !cursor.eat("]") {
+                    total += sum(cursor)?;
+                    cursor.eat(",");
+                }
+                Ok(total)
+            } else {
+                cursor.number()
+            }
+        }
+        let mut cursor = Cursor::new("[1,[2,-3],[[4]]]");
+        assert_eq!(sum(&mut cursor), Ok(4));
+        assert!(cursor.end().is_ok());
+        let mut cursor = Cursor::new("[1,\n[x]]");
+        let error = sum(&mut cursor).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 2, "[x]]")
+        );
+    }
+}
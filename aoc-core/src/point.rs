@@ -0,0 +1,484 @@
+//! Points in two and three dimensions over any signed integer.
+//!
+//! `y` grows downwards, as the puzzles draw their grids, so [`Direction::Up`] is `(0, -1)` and
+//! turning clockwise goes up, right, down, left.
+
+use eyre::{Report, Result, WrapErr};
+use std::fmt::{self, Debug, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// The signed integers points can be made of.
+pub trait Number:
+    Copy
+    + Ord
+    + Default
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+impl_number!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Implements the componentwise arithmetic, and the metrics built on it, for a point type.
+macro_rules! impl_point {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Number> $point<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $point { $($field),+ }
+            }
+
+            pub fn origin() -> Self {
+                Self::default()
+            }
+
+            pub fn abs(self) -> Self {
+                $point { $($field: self.$field.abs()),+ }
+            }
+
+            /// Each component as -1, 0 or 1, which is one step in this point's direction.
+            pub fn signum(self) -> Self {
+                $point { $($field: self.$field.signum()),+ }
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                $point { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            pub fn max(self, other: Self) -> Self {
+                $point { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            /// The taxicab distance, moving along one axis at a time.
+            pub fn manhattan(self, other: Self) -> T {
+                let difference = (self - other).abs();
+                T::ZERO $(+ difference.$field)+
+            }
+
+            /// The king's move distance, moving along any number of axes at once.
+            pub fn chebyshev(self, other: Self) -> T {
+                let difference = (self - other).abs();
+                [$(difference.$field),+].into_iter().fold(T::ZERO, T::max)
+            }
+
+            pub fn euclidean(self, other: Self) -> f64 {
+                let difference = self - other;
+                (0.0 $(+ difference.$field.as_f64().powi(2))+).sqrt()
+            }
+
+            /// One step from here towards `target`, diagonally if need be.
+            pub fn step_towards(self, target: Self) -> Self {
+                self + (target - self).signum()
+            }
+
+            /// Every point from here to `end` inclusive, stepping with [`Self::step_towards`].
+            /// The line is only straight if it is along an axis or a diagonal.
+            pub fn line_to(self, end: Self) -> impl Iterator<Item = Self> {
+                let mut next = Some(self);
+                std::iter::from_fn(move || {
+                    let current = next?;
+                    next = (current != end).then(|| current.step_towards(end));
+                    Some(current)
+                })
+            }
+        }
+
+        impl<T: Number> Add for $point<T> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Number> Sub for $point<T> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Number> Mul<T> for $point<T> {
+            type Output = Self;
+            fn mul(self, scale: T) -> Self {
+                $point { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl<T: Number> Neg for $point<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Number> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Number> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        /// Parses `1,2` as well as `x=1, y=2`.
+        impl<T: FromStr> FromStr for $point<T>
+        where
+            Report: From<T::Err>,
+        {
+            type Err = Report;
+            fn from_str(s: &str) -> Result<Self> {
+                let mut components = s.split(',');
+                $(
+                    let $field = parse_component(components.next(), stringify!($field))
+                        .wrap_err_with(|| format!("bad point [{s}]"))?;
+                )+
+                if components.next().is_some() {
+                    return Err(Report::msg(format!("too many components in point [{s}]")));
+                }
+                Ok($point { $($field),+ })
+            }
+        }
+    };
+}
+
+fn parse_component<T: FromStr>(component: Option<&str>, name: &str) -> Result<T>
+where
+    Report: From<T::Err>,
+{
+    let component = component
+        .ok_or_else(|| Report::msg(format!("missing {name}")))?
+        .trim();
+    let value = match component.split_once('=') {
+        Some((label, value)) if label.trim() == name => value.trim(),
+        Some((label, _)) => {
+            return Err(Report::msg(format!("expected {name}=, found {label}=")));
+        }
+        None => component,
+    };
+    Ok(value.parse()?)
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+
+impl<T: Number> Point2<T> {
+    /// The four points sharing an edge with this one, clockwise from up.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self + direction.offset())
+    }
+
+    /// The eight points surrounding this one, clockwise from up.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| self + direction.offset())
+    }
+
+    /// A quarter turn clockwise about the origin.
+    pub fn rotate_clockwise(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// A quarter turn counterclockwise about the origin.
+    pub fn rotate_counterclockwise(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+}
+
+impl<T: Number> Point3<T> {
+    /// The six points sharing a face with this one.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            Point3::new(-one, zero, zero),
+            Point3::new(one, zero, zero),
+            Point3::new(zero, -one, zero),
+            Point3::new(zero, one, zero),
+            Point3::new(zero, zero, -one),
+            Point3::new(zero, zero, one),
+        ]
+        .into_iter()
+        .map(move |offset| self + offset)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self as usize + 1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self as usize + 3)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self as usize + 2)
+    }
+
+    /// One step in this direction.
+    pub fn offset<T: Number>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Direction::Up => Point2::new(zero, -one),
+            Direction::Right => Point2::new(one, zero),
+            Direction::Down => Point2::new(zero, one),
+            Direction::Left => Point2::new(-one, zero),
+        }
+    }
+}
+
+/// Reads `U`, `R`, `D` and `L` as well as the arrows `^`, `>`, `v` and `<`.
+impl TryFrom<char> for Direction {
+    type Error = Report;
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            'U' | '^' => Ok(Direction::Up),
+            'R' | '>' => Ok(Direction::Right),
+            'D' | 'v' => Ok(Direction::Down),
+            'L' | '<' => Ok(Direction::Left),
+            other => Err(Report::msg(format!("unknown direction [{other}]"))),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Report;
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(Report::msg(format!("unknown direction [{s}]"))),
+        }
+    }
+}
+
+/// The compass points, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn rotate_right(self) -> Self {
+        Self::from_index(self as usize + 1)
+    }
+
+    /// An eighth of a turn counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        Self::from_index(self as usize + 7)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self as usize + 4)
+    }
+
+    /// One step in this direction.
+    pub fn offset<T: Number>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (x, y) = match self {
+            Direction8::North => (zero, -one),
+            Direction8::NorthEast => (one, -one),
+            Direction8::East => (one, zero),
+            Direction8::SouthEast => (one, one),
+            Direction8::South => (zero, one),
+            Direction8::SouthWest => (-one, one),
+            Direction8::West => (-one, zero),
+            Direction8::NorthWest => (-one, -one),
+        };
+        Point2::new(x, y)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::North,
+            Direction::Right => Direction8::East,
+            Direction::Down => Direction8::South,
+            Direction::Left => Direction8::West,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(4, 2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(b - a, Point2::new(3, 4));
+        assert_eq!(a * 3, Point2::new(3, -6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        let c = Point3::new(1i64, 2, 3);
+        assert_eq!(c.manhattan(Point3::origin()), 6);
+        assert_eq!(c.neighbors6().count(), 6);
+    }
+
+    #[test]
+    fn steps_along_lines() {
+        let line: Vec<_> = Point2::new(0, 0).line_to(Point2::new(3, -3)).collect();
+        assert_eq!(line, [(0, 0), (1, -1), (2, -2), (3, -3)].map(Point2::from));
+        assert_eq!(Point2::new(2, 2).line_to(Point2::new(2, 2)).count(), 1);
+        assert_eq!(
+            Point2::new(0, 0).step_towards(Point2::new(5, -1)),
+            Point2::new(1, -1)
+        );
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction8::NorthWest.rotate_right(), Direction8::North);
+        let up: Point2<i32> = Direction::Up.offset();
+        assert_eq!(up.rotate_clockwise(), Direction::Right.offset());
+        assert_eq!(up.rotate_counterclockwise(), Direction::Left.offset());
+        assert_eq!("v".parse::<Direction>().unwrap(), Direction::Down);
+    }
+
+    #[test]
+    fn parses_both_forms() {
+        assert_eq!("3,-4".parse::<Point2<i32>>().unwrap(), Point2::new(3, -4));
+        assert_eq!(
+            "x=3, y=-4".parse::<Point2<i32>>().unwrap(),
+            Point2::new(3, -4)
+        );
+        assert_eq!(
+            "1,2,3".parse::<Point3<i64>>().unwrap(),
+            Point3::new(1, 2, 3)
+        );
+        assert!("y=3, x=4".parse::<Point2<i32>>().is_err());
+        assert!("1,2,3".parse::<Point2<i32>>().is_err());
+        assert!("1".parse::<Point2<i32>>().is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Flood fills and connected regions of a [`Grid`].
+//!
+//! Cells join a region through their up, right, down and left neighbors, and a boundary
+//! predicate decides which cells are walls between regions rather than part of one. Fills use
+//! an explicit stack, so a region can be as large as the grid.
+
+use std::fmt::Write;
+
+use crate::grid::{Grid, Position};
+
+/// Foreground colors regions are drawn in, taken in turn by label.
+const COLORS: [&str; 6] = [
+    "\x1b[31m", "\x1b[32m", "\x1b[33m", "\x1b[34m", "\x1b[35m", "\x1b[36m",
+];
+const RESET: &str = "\x1b[0m";
+
+/// The positions reachable from `start` without crossing a `boundary` cell, in the order they
+/// were reached. Empty if `start` is itself a boundary.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: Position,
+    boundary: impl Fn(&T) -> bool,
+) -> Vec<Position> {
+    let mut seen = Grid::filled(grid.rows(), grid.columns(), false);
+    fill(grid, start, &boundary, &mut seen)
+}
+
+fn fill<T>(
+    grid: &Grid<T>,
+    start: Position,
+    boundary: &impl Fn(&T) -> bool,
+    seen: &mut Grid<bool>,
+) -> Vec<Position> {
+    let mut cells = Vec::new();
+    if boundary(&grid[start]) || seen[start] {
+        return cells;
+    }
+    seen[start] = true;
+    let mut stack = vec![start];
+    while let Some(position) = stack.pop() {
+        cells.push(position);
+        for neighbor in grid.neighbors4(position) {
+            if !seen[neighbor] && !boundary(&grid[neighbor]) {
+                seen[neighbor] = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+    cells
+}
+
+/// One connected region of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The region's index in [`Regions::regions`].
+    pub label: usize,
+    /// Its cells, in the order the fill reached them.
+    pub cells: Vec<Position>,
+    /// The smallest row and column of any of its cells.
+    pub top_left: Position,
+    /// The largest row and column of any of its cells.
+    pub bottom_right: Position,
+}
+
+impl Region {
+    fn new(label: usize, cells: Vec<Position>) -> Self {
+        let top_left = (
+            cells.iter().map(|&(row, _)| row).min().unwrap_or_default(),
+            cells
+                .iter()
+                .map(|&(_, column)| column)
+                .min()
+                .unwrap_or_default(),
+        );
+        let bottom_right = (
+            cells.iter().map(|&(row, _)| row).max().unwrap_or_default(),
+            cells
+                .iter()
+                .map(|&(_, column)| column)
+                .max()
+                .unwrap_or_default(),
+        );
+        Region {
+            label,
+            cells,
+            top_left,
+            bottom_right,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The position of the region's smallest value in `grid`, the first in reading order if
+    /// there is a tie.
+    pub fn lowest<T: Ord>(&self, grid: &Grid<T>) -> Position {
+        *self
+            .cells
+            .iter()
+            .min_by_key(|&&position| (&grid[position], position))
+            .expect("regions are never empty")
+    }
+}
+
+/// Every region of a grid, with each cell labelled by the region it is in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    labels: Grid<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Splits `grid` into regions separated by `boundary` cells, labelled in reading order of
+    /// their first cell.
+    pub fn find<T>(grid: &Grid<T>, boundary: impl Fn(&T) -> bool) -> Self {
+        let mut seen = Grid::filled(grid.rows(), grid.columns(), false);
+        let mut labels = Grid::filled(grid.rows(), grid.columns(), None);
+        let mut regions = Vec::new();
+        for position in grid.positions() {
+            let cells = fill(grid, position, &boundary, &mut seen);
+            if cells.is_empty() {
+                continue;
+            }
+            for &cell in &cells {
+                labels[cell] = Some(regions.len());
+            }
+            regions.push(Region::new(regions.len(), cells));
+        }
+        Regions { labels, regions }
+    }
+
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// The label of the region `position` is in, or `None` for a boundary.
+    pub fn label(&self, position: Position) -> Option<usize> {
+        self.labels[position]
+    }
+
+    /// The grid as text, one row per line, drawing each cell with `to_char` in its region's
+    /// color. Boundaries are left uncolored.
+    pub fn render<T>(&self, grid: &Grid<T>, to_char: impl Fn(&T) -> char) -> String {
+        let mut text = String::new();
+        for row in 0..grid.rows() {
+            for column in 0..grid.columns() {
+                let position = (row, column);
+                let c = to_char(&grid[position]);
+                match self.label(position) {
+                    Some(label) => {
+                        let color = COLORS[label % COLORS.len()];
+                        write!(text, "{color}{c}{RESET}").unwrap();
+                    }
+                    None => text.push(c),
+                }
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(text: &str) -> Grid<char> {
+        Grid::parse(text, Ok).unwrap()
+    }
+
+    #[test]
+    fn fills_around_walls() {
+        let grid = map("..#..\n..#..\n###..\n.....\n");
+        assert_eq!(flood_fill(&grid, (0, 2), |&c| c == '#'), vec![]);
+        let mut corner = flood_fill(&grid, (1, 1), |&c| c == '#');
+        corner.sort();
+        assert_eq!(corner, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(flood_fill(&grid, (3, 0), |&c| c == '#').len(), 11);
+    }
+
+    #[test]
+    fn labels_regions() {
+        let grid = map("ab#c\n#b#c\nbb#c\n");
+        let regions = Regions::find(&grid, |&c| c == '#');
+        let sizes: Vec<_> = regions.regions().iter().map(Region::size).collect();
+        assert_eq!(sizes, vec![5, 3]);
+        let first = &regions.regions()[0];
+        assert_eq!((first.top_left, first.bottom_right), ((0, 0), (2, 1)));
+        assert_eq!(first.lowest(&grid), (0, 0));
+        assert_eq!(regions.label((2, 0)), Some(0));
+        assert_eq!(regions.label((1, 0)), None);
+        assert_eq!(regions.label((1, 3)), Some(1));
+
+        // the boundary can be any predicate, here leaving only the b's
+        let regions = Regions::find(&grid, |&c| c != 'b');
+        assert_eq!(regions.regions().len(), 1);
+        assert_eq!(regions.regions()[0].top_left, (0, 0));
+        assert_eq!(regions.label((0, 0)), None);
+    }
+
+    #[test]
+    fn renders_in_color() {
+        let grid = map("a#b\n");
+        let regions = Regions::find(&grid, |&c| c == '#');
+        assert_eq!(
+            regions.render(&grid, |&c| c),
+            "\x1b[31ma\x1b[0m#\x1b[32mb\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Shortest path searches over any graph given as a neighbor function.
+//!
+//! Every search records, for each node it reaches, its distance and the nodes it can be reached
+//! from along a shortest path. Paths are rebuilt from those predecessors on demand, so nothing
+//! is cloned while searching. A search stops as soon as it settles a node `is_goal` accepts;
+//! pass `|_| false` to explore everything reachable and use the distance map.
+//!
+//! Step costs must not be negative. Steps that cost nothing are fine, even around a cycle:
+//! starts are never given predecessors, and paths never visit a node twice.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: distances to every node it reached, and the shortest routes to them.
+/// If the search stopped at a goal, nodes further out than the goal may only have tentative
+/// distances.
+#[derive(Debug, Clone)]
+pub struct Explored<N, C> {
+    distances: HashMap<N, C>,
+    predecessors: HashMap<N, Vec<N>>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Explored<N, C> {
+    fn new() -> Self {
+        Explored {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// The first goal the search settled, which is the closest one.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// The distance to the goal, if one was reached.
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// One shortest path from a start to `node`, both ends included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last()?)
+            .and_then(|predecessors| predecessors.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from a start to `node`, both ends included.
+    pub fn all_paths_to(&self, node: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return Vec::new();
+        }
+        let mut paths = Vec::new();
+        // partial paths are built backwards from `node` and reversed once they reach a start
+        let mut partial = vec![vec![node.clone()]];
+        while let Some(path) = partial.pop() {
+            match path.last().and_then(|tip| self.predecessors.get(tip)) {
+                Some(predecessors) => {
+                    // a step back to a node already on the path can only close a zero-cost cycle
+                    for previous in predecessors
+                        .iter()
+                        .filter(|&previous| !path.contains(previous))
+                    {
+                        let mut longer = path.clone();
+                        longer.push(previous.clone());
+                        partial.push(longer);
+                    }
+                }
+                None => paths.push(path.into_iter().rev().collect()),
+            }
+        }
+        paths
+    }
+
+    /// Records that `node` is `distance` away via `from`, keeping `from` as another way in if
+    /// it ties the best distance so far. Returns whether the node needs (re)visiting.
+    fn relax(&mut self, node: N, distance: C, from: &N) -> bool
+    where
+        C: Ord,
+    {
+        match self.distances.get(&node).map(|known| distance.cmp(known)) {
+            Some(Ordering::Greater) => false,
+            Some(Ordering::Equal) => {
+                // starts are the only reached nodes without predecessors, and a zero-cost way
+                // back into one must not become part of a path
+                if let Some(predecessors) = self.predecessors.get_mut(&node) {
+                    predecessors.push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(node.clone(), distance);
+                self.predecessors.insert(node, vec![from.clone()]);
+                true
+            }
+        }
+    }
+}
+
+/// Breadth first search from `start`, where every step costs one.
+pub fn bfs<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Explored<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_multi([start], neighbors, is_goal)
+}
+
+/// Breadth first search from all of `starts` at once, which finds the distance from each node
+/// to its closest start.
+pub fn bfs_multi<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Explored<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut explored = Explored::new();
+    let mut frontier = VecDeque::new();
+    for start in starts {
+        if explored.distances.insert(start.clone(), 0).is_none() {
+            frontier.push_back(start);
+        }
+    }
+    while let Some(node) = frontier.pop_front() {
+        if is_goal(&node) {
+            explored.goal = Some(node);
+            break;
+        }
+        let distance = explored.distances[&node] + 1;
+        for neighbor in neighbors(&node) {
+            if explored.relax(neighbor.clone(), distance, &node) {
+                frontier.push_back(neighbor);
+            }
+        }
+    }
+    explored
+}
+
+/// A frontier entry, ordered so that the heap pops the lowest estimate first.
+struct Entry<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.estimate.cmp(&other.estimate).reverse()
+    }
+}
+
+/// Dijkstra's algorithm from `start`, where `neighbors` gives each next node with the cost of
+/// the step to it.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Explored<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_multi([start], neighbors, |_| C::default(), is_goal)
+}
+
+/// Dijkstra's algorithm from all of `starts` at once.
+pub fn dijkstra_multi<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Explored<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_multi(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// A* from `start`. `heuristic` must never overestimate the remaining cost to a goal, or the
+/// path found may not be the shortest.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Explored<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_multi([start], neighbors, heuristic, is_goal)
+}
+
+/// A* from all of `starts` at once. Costs start at `C::default()`, which should be zero.
+pub fn astar_multi<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Explored<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut explored = Explored::new();
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        if explored
+            .distances
+            .insert(start.clone(), C::default())
+            .is_none()
+        {
+            frontier.push(Entry {
+                estimate: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+    while let Some(Entry { cost, node, .. }) = frontier.pop() {
+        if explored
+            .distances
+            .get(&node)
+            .is_some_and(|&best| cost > best)
+        {
+            // a cheaper way here was found after this entry was pushed
+            continue;
+        }
+        if is_goal(&node) {
+            explored.goal = Some(node);
+            break;
+        }
+        for (neighbor, step) in neighbors(&node) {
+            let cost = cost + step;
+            if explored.relax(neighbor.clone(), cost, &node) {
+                frontier.push(Entry {
+                    estimate: cost + heuristic(&neighbor),
+                    cost,
+                    node: neighbor,
+                });
+            }
+        }
+    }
+    explored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a diamond with two shortest routes from 0 to 3, and a slow direct edge
+    fn diamond(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 1), (3, 5)],
+            1 | 2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_finds_every_shortest_path() {
+        let explored = bfs(0, |n| diamond(n).into_iter().map(|(n, _)| n), |_| false);
+        assert_eq!(explored.distance(&3), Some(1));
+        let explored = bfs(
+            0,
+            |&n| if n < 2 { vec![n + 1, n + 2] } else { vec![] },
+            |&n| n == 2,
+        );
+        assert_eq!(explored.goal_distance(), Some(1));
+        assert_eq!(explored.path_to(&2), Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn dijkstra_keeps_ties() {
+        let explored = dijkstra(0, diamond, |&n| n == 3);
+        assert_eq!(explored.goal(), Some(&3));
+        assert_eq!(explored.goal_distance(), Some(2));
+        let mut paths = explored.all_paths_to(&3);
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+        assert_eq!(explored.path_to(&3).map(|path| path.len()), Some(3));
+        assert_eq!(explored.path_to(&9), None);
+    }
+
+    #[test]
+    fn zero_cost_cycles_end_at_the_start() {
+        // 0 and 1 lead to each other for free, as do 2 and 3, and 1 to 2 costs one
+        let free = |&n: &u8| match n {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            2 => vec![(3, 0)],
+            _ => vec![(2, 0)],
+        };
+        let explored = dijkstra(0, free, |_| false);
+        assert_eq!(explored.path_to(&0), Some(vec![0]));
+        assert_eq!(explored.path_to(&1), Some(vec![0, 1]));
+        assert_eq!(explored.path_to(&3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(explored.all_paths_to(&3), vec![vec![0, 1, 2, 3]]);
+        let explored = bfs_multi([0, 1], |&n| free(&n).into_iter().map(|(n, _)| n), |_| false);
+        assert_eq!(explored.path_to(&1), Some(vec![1]));
+        assert_eq!(explored.all_paths_to(&0), vec![vec![0]]);
+    }
+
+    #[test]
+    fn astar_and_multi_source_agree_with_dijkstra() {
+        let line = |&n: &i32| [(n - 1, 1), (n + 1, 1)];
+        let explored = astar(0, line, |&n| (10 - n).abs(), |&n| n == 10);
+        assert_eq!(explored.goal_distance(), Some(10));
+        // nothing left of the start should have been settled
+        assert!(explored.distances().keys().all(|&n| n >= -1));
+        let explored = dijkstra_multi([0, 20], line, |&n| n == 13);
+        assert_eq!(explored.goal_distance(), Some(7));
+        assert_eq!(explored.path_to(&13).unwrap().first(), Some(&20));
+    }
+}
@@ -0,0 +1,264 @@
+//! An unbounded grid that only stores the cells that have been set, addressed by `(x, y)` with
+//! `y` growing downwards, as the puzzles draw them.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use crate::grid::Grid;
+
+/// An `(x, y)` position in a [`SparseGrid`].
+pub type Coordinate = (i64, i64);
+
+/// The smallest rectangle holding a set of coordinates, inclusive at both corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+impl Bounds {
+    pub fn new(min: Coordinate, max: Coordinate) -> Self {
+        Bounds { min, max }
+    }
+
+    pub fn point(coordinate: Coordinate) -> Self {
+        Bounds::new(coordinate, coordinate)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1).max(0) as usize
+    }
+
+    pub fn contains(&self, (x, y): Coordinate) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    /// Grows the bounds just enough to hold `coordinate`.
+    pub fn include(&mut self, (x, y): Coordinate) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    fn on_edge(&self, (x, y): Coordinate) -> bool {
+        x == self.min.0 || x == self.max.0 || y == self.min.1 || y == self.max.1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    // keyed by (y, x) so that iteration goes in reading order
+    cells: BTreeMap<(i64, i64), T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: BTreeMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The bounds of every set cell, or `None` while the grid is empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn insert(&mut self, (x, y): Coordinate, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include((x, y)),
+            None => self.bounds = Some(Bounds::point((x, y))),
+        }
+        self.cells.insert((y, x), value)
+    }
+
+    pub fn remove(&mut self, (x, y): Coordinate) -> Option<T> {
+        let removed = self.cells.remove(&(y, x))?;
+        // only a cell on the edge can have been holding the bounds out
+        if self.bounds.is_some_and(|bounds| bounds.on_edge((x, y))) {
+            self.bounds = self.coordinates().fold(None, |bounds, coordinate| {
+                let mut bounds = bounds.unwrap_or(Bounds::point(coordinate));
+                bounds.include(coordinate);
+                Some(bounds)
+            });
+        }
+        Some(removed)
+    }
+
+    pub fn get(&self, (x, y): Coordinate) -> Option<&T> {
+        self.cells.get(&(y, x))
+    }
+
+    pub fn get_mut(&mut self, (x, y): Coordinate) -> Option<&mut T> {
+        self.cells.get_mut(&(y, x))
+    }
+
+    pub fn contains(&self, (x, y): Coordinate) -> bool {
+        self.cells.contains_key(&(y, x))
+    }
+
+    /// Every set cell with its coordinate, top to bottom and then left to right.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Coordinate, &T)> {
+        self.cells.iter().map(|(&(y, x), value)| ((x, y), value))
+    }
+
+    pub fn coordinates(&self) -> impl DoubleEndedIterator<Item = Coordinate> + '_ {
+        self.cells.keys().map(|&(y, x)| (x, y))
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// Renders the cells inside `viewport` one row per line, drawing each with `to_char`,
+    /// which is given `None` for the unset cells.
+    pub fn render(&self, viewport: Bounds, to_char: impl Fn(Option<&T>) -> char) -> String {
+        let mut text = String::with_capacity((viewport.width() + 1) * viewport.height());
+        for y in viewport.min.1..=viewport.max.1 {
+            for x in viewport.min.0..=viewport.max.0 {
+                text.push(to_char(self.get((x, y))));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Displays the whole grid, from its top left set cell to its bottom right one.
+    pub fn display<F: Fn(Option<&T>) -> char>(&self, to_char: F) -> SparseGridDisplay<'_, T, F> {
+        SparseGridDisplay {
+            grid: self,
+            to_char,
+        }
+    }
+
+    /// Copies the grid into a dense one covering its bounds, filling unset cells with `empty`.
+    /// Returns the dense grid with the coordinate of its top left cell.
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Coordinate)
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return (Grid::filled(0, 0, empty), (0, 0));
+        };
+        let mut grid = Grid::filled(bounds.height(), bounds.width(), empty);
+        for ((x, y), value) in self.iter() {
+            let position = ((y - bounds.min.1) as usize, (x - bounds.min.0) as usize);
+            grid[position] = value.clone();
+        }
+        (grid, bounds.min)
+    }
+
+    /// Copies the cells of a dense grid that `keep` accepts, with its top left cell at `(0, 0)`.
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, value)| keep(value))
+            .map(|((row, column), value)| ((column as i64, row as i64), value.clone()))
+            .collect()
+    }
+}
+
+impl<T> FromIterator<(Coordinate, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coordinate, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Coordinate, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Coordinate, T)>>(&mut self, iter: I) {
+        for (coordinate, value) in iter {
+            self.insert(coordinate, value);
+        }
+    }
+}
+
+pub struct SparseGridDisplay<'a, T, F> {
+    grid: &'a SparseGrid<T>,
+    to_char: F,
+}
+
+impl<T, F: Fn(Option<&T>) -> char> Display for SparseGridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.grid.bounds() {
+            Some(bounds) => f.write_str(&self.grid.render(bounds, &self.to_char)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dots(coordinates: &[Coordinate]) -> SparseGrid<()> {
+        coordinates.iter().map(|&c| (c, ())).collect()
+    }
+
+    fn draw(cell: Option<&()>) -> char {
+        if cell.is_some() {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = dots(&[(2, 1), (-1, 3), (0, 0)]);
+        assert_eq!(grid.bounds(), Some(Bounds::new((-1, 0), (2, 3))));
+        assert_eq!(grid.remove((1, 1)), None);
+        grid.remove((-1, 3));
+        assert_eq!(grid.bounds(), Some(Bounds::new((0, 0), (2, 1))));
+        grid.remove((0, 0));
+        grid.remove((2, 1));
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn iterates_in_reading_order() {
+        let grid = dots(&[(5, 2), (-3, 2), (9, -1)]);
+        assert_eq!(
+            grid.coordinates().collect::<Vec<_>>(),
+            [(9, -1), (-3, 2), (5, 2)]
+        );
+    }
+
+    #[test]
+    fn renders_and_converts() {
+        let grid = dots(&[(-1, -1), (1, 0), (0, 1)]);
+        assert_eq!(grid.display(draw).to_string(), "#..\n..#\n.#.\n");
+        assert_eq!(grid.render(Bounds::new((0, 0), (1, 1)), draw), ".#\n#.\n");
+        let marked: SparseGrid<char> = grid.iter().map(|(c, _)| (c, '#')).collect();
+        let (dense, origin) = marked.to_grid('.');
+        assert_eq!(origin, (-1, -1));
+        assert_eq!(dense.to_string(), "#..\n..#\n.#.\n");
+        let back = SparseGrid::from_grid(&dense, |&c| c == '#');
+        assert_eq!(
+            back.display(|c| c.map_or('.', |&c| c)).to_string(),
+            "#..\n..#\n.#.\n"
+        );
+        assert_eq!(back.bounds(), Some(Bounds::new((0, 0), (2, 2))));
+    }
+}
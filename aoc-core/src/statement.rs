@@ -0,0 +1,427 @@
+//! Puzzle statements kept as `problems/dayNN/problem.md`.
+//!
+//! Statements are plain text with fenced code blocks. A block whose info string starts with
+//! `example` is a worked example, and may carry the expected answers as `part=answer` pairs:
+//!
+//! ````text
+//! ```example part_one=3068 part_two=1514285714288
+//! >>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>
+//! ```
+//! ````
+//!
+//! The parts are `part_one` and `part_two`. An answer that spans several lines, such as a
+//! rendered image, goes in a block of its own straight after the example, with an info string
+//! of `answer` and the part:
+//!
+//! ````text
+//! ```answer part_two
+//! #####
+//! #...#
+//! ```
+//! ````
+//!
+//! Examples are extracted next to the statement as `exampleN` fixtures, with their answers in
+//! `exampleN.answers`, one `part: answer` per line, or `part:` and then the lines of a block
+//! answer.
+
+use eyre::{Report, Result, WrapErr};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::problem_dir;
+
+const FENCE: &str = "```";
+const BOLD: &str = "\x1b[1m";
+const CODE: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+pub fn problem_statement_path(year: u16, day: u8) -> PathBuf {
+    problem_dir(year, day).join("problem.md")
+}
+
+pub fn read_statement(year: u16, day: u8) -> Result<String> {
+    let path = problem_statement_path(year, day);
+    fs::read_to_string(&path).wrap_err_with(|| format!("no statement at {}", path.display()))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<(String, String)>,
+}
+
+impl Example {
+    pub fn answers_file(&self) -> String {
+        self.answers
+            .iter()
+            .map(|(part, answer)| {
+                if answer.contains('\n') {
+                    format!("{part}:\n{answer}\n")
+                } else {
+                    format!("{part}: {answer}\n")
+                }
+            })
+            .collect()
+    }
+
+    fn add_answer(&mut self, part: &str, answer: String) -> Result<()> {
+        if !matches!(part, "part_one" | "part_two") {
+            return Err(Report::msg(format!(
+                "unknown part {part:?}, expected part_one or part_two"
+            )));
+        }
+        if answer.trim().is_empty() {
+            return Err(Report::msg(format!("the answer to {part} is empty")));
+        }
+        if self.answers.iter().any(|(known, _)| known == part) {
+            return Err(Report::msg(format!("{part} is answered more than once")));
+        }
+        self.answers.push((part.to_string(), answer));
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Block<'a> {
+    Text(&'a str),
+    Code { info: &'a str, lines: Vec<&'a str> },
+}
+
+fn blocks(markdown: &str) -> Result<Vec<Block<'_>>> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines().enumerate();
+    while let Some((number, line)) = lines.next() {
+        if let Some(info) = line.strip_prefix(FENCE) {
+            let mut code = Vec::new();
+            loop {
+                match lines.next() {
+                    Some((_, line)) if line.starts_with(FENCE) => break,
+                    Some((_, line)) => code.push(line),
+                    None => {
+                        return Err(Report::msg(format!(
+                            "the fence opened on line {} is never closed",
+                            number + 1
+                        )))
+                    }
+                }
+            }
+            blocks.push(Block::Code {
+                info: info.trim(),
+                lines: code,
+            });
+        } else {
+            blocks.push(Block::Text(line));
+        }
+    }
+    Ok(blocks)
+}
+
+/// The `part=answer` pairs of an example block's info string, or `None` if it is not one.
+fn example_answers(info: &str) -> Option<Result<Vec<(&str, &str)>>> {
+    let mut words = info.split_whitespace();
+    if words.next() != Some("example") {
+        return None;
+    }
+    Some(
+        words
+            .map(|word| {
+                word.split_once('=')
+                    .ok_or_else(|| Report::msg(format!("expected part=answer, found {word}")))
+            })
+            .collect(),
+    )
+}
+
+/// The part a block answer is for, or `None` if the block is not an answer.
+fn block_answer(info: &str) -> Option<Result<&str>> {
+    let mut words = info.split_whitespace();
+    if words.next() != Some("answer") {
+        return None;
+    }
+    Some(match (words.next(), words.next()) {
+        (Some(part), None) => Ok(part),
+        _ => Err(Report::msg(format!(
+            "expected `answer` and a part, found {info:?}"
+        ))),
+    })
+}
+
+/// The worked examples of a statement, in the order they appear.
+pub fn examples(markdown: &str) -> Result<Vec<Example>> {
+    let mut examples: Vec<Example> = Vec::new();
+    // whether the last block was an example or one of its answers, which the next answer
+    // block may follow
+    let mut answerable = false;
+    for block in blocks(markdown)? {
+        let Block::Code { info, lines } = block else {
+            answerable &= matches!(block, Block::Text(line) if line.trim().is_empty());
+            continue;
+        };
+        if let Some(answers) = example_answers(info) {
+            let mut example = Example {
+                input: lines.join("\n") + "\n",
+                answers: Vec::new(),
+            };
+            for (part, answer) in answers? {
+                example.add_answer(part, answer.to_string())?;
+            }
+            examples.push(example);
+            answerable = true;
+        } else if let Some(part) = block_answer(info) {
+            let example = examples
+                .last_mut()
+                .filter(|_| answerable)
+                .ok_or_else(|| Report::msg("an answer block must follow an example"))?;
+            example.add_answer(part?, lines.join("\n"))?;
+        } else {
+            answerable = false;
+        }
+    }
+    Ok(examples)
+}
+
+/// Writes each example of a day's statement to `exampleN` and `exampleN.answers`, the latter
+/// empty if the statement gives no answers for it.
+pub fn extract_fixtures(year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    let examples = examples(&read_statement(year, day)?)?;
+    write_fixtures(&problem_dir(year, day), &examples)
+}
+
+/// Writes the fixtures for `examples` into `dir`. Refuses to write anything if that would
+/// change a fixture that is already there, unless it is empty, as a new day's placeholder is.
+fn write_fixtures(dir: &Path, examples: &[Example]) -> Result<Vec<PathBuf>> {
+    let fixtures: Vec<(PathBuf, String)> = examples
+        .iter()
+        .enumerate()
+        .flat_map(|(index, example)| {
+            let input = dir.join(format!("example{}", index + 1));
+            let answers = input.with_extension("answers");
+            [
+                (input, example.input.clone()),
+                (answers, example.answers_file()),
+            ]
+        })
+        .collect();
+    for (path, content) in &fixtures {
+        if let Ok(existing) = fs::read_to_string(path) {
+            if !existing.is_empty() && existing != *content {
+                return Err(Report::msg(format!(
+                    "{} differs from the statement, refusing to overwrite it",
+                    path.display()
+                )));
+            }
+        }
+    }
+    for (path, content) in &fixtures {
+        fs::write(path, content).wrap_err_with(|| format!("could not write {}", path.display()))?;
+    }
+    Ok(fixtures.into_iter().map(|(path, _)| path).collect())
+}
+
+fn is_heading(line: &str) -> bool {
+    line.starts_with('#') || (line.starts_with("---") && line.ends_with("---"))
+}
+
+fn highlight_inline_code(line: &str) -> String {
+    line.split('`')
+        .enumerate()
+        .map(|(index, piece)| {
+            if index % 2 == 1 {
+                format!("{CODE}{piece}{RESET}")
+            } else {
+                piece.to_string()
+            }
+        })
+        .collect()
+}
+
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    let mut current = String::new();
+    for word in line.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > width {
+            wrapped.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    wrapped.push(current);
+    wrapped
+}
+
+/// Renders a statement for the terminal: prose is wrapped to `width`, code blocks are
+/// indented, and examples are labelled with their answers. Color is only used if asked for.
+pub fn render(markdown: &str, width: usize, color: bool) -> Result<String> {
+    let paint = |style: &str, text: &str| {
+        if color {
+            format!("{style}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    };
+    let mut rendered = String::new();
+    for block in blocks(markdown)? {
+        match block {
+            Block::Text(line) if is_heading(line) => {
+                rendered.push_str(&paint(BOLD, line.trim_start_matches('#').trim()));
+                rendered.push('\n');
+            }
+            Block::Text(line) => {
+                for wrapped in wrap(line, width) {
+                    if color {
+                        rendered.push_str(&highlight_inline_code(&wrapped));
+                    } else {
+                        rendered.push_str(&wrapped);
+                    }
+                    rendered.push('\n');
+                }
+            }
+            Block::Code { info, lines } => {
+                if let Some(part) = block_answer(info) {
+                    rendered.push_str(&paint(BOLD, &format!("Answer ({}):", part?)));
+                    rendered.push('\n');
+                } else if let Some(answers) = example_answers(info) {
+                    let answers = answers?
+                        .into_iter()
+                        .map(|(part, answer)| format!("{part} = {answer}"))
+                        .collect::<Vec<_>>();
+                    let label = if answers.is_empty() {
+                        "Example:".to_string()
+                    } else {
+                        format!("Example ({}):", answers.join(", "))
+                    };
+                    rendered.push_str(&paint(BOLD, &label));
+                    rendered.push('\n');
+                }
+                for line in lines {
+                    rendered.push_str("    ");
+                    rendered.push_str(&paint(CODE, line));
+                    rendered.push('\n');
+                }
+            }
+        }
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATEMENT: &str = "--- Day 1: Test ---
+Some prose with `code` in it.
+
+```
+not an example
+```
+
+```example part_one=3 part_two=12
+1
+2
+```
+";
+
+    #[test]
+    fn extracts_examples() {
+        let examples = examples(STATEMENT).unwrap();
+        assert_eq!(
+            examples,
+            vec![Example {
+                input: "1\n2\n".to_string(),
+                answers: vec![
+                    ("part_one".to_string(), "3".to_string()),
+                    ("part_two".to_string(), "12".to_string())
+                ],
+            }]
+        );
+        assert_eq!(examples[0].answers_file(), "part_one: 3\npart_two: 12\n");
+    }
+
+    #[test]
+    fn extracts_block_answers() {
+        let statement = "```example part_one=17\n#.\n.#\n```\n\n```answer part_two\n##\n#.\n```\n";
+        let examples = examples(statement).unwrap();
+        assert_eq!(
+            examples[0].answers,
+            vec![
+                ("part_one".to_string(), "17".to_string()),
+                ("part_two".to_string(), "##\n#.".to_string())
+            ]
+        );
+        assert_eq!(
+            examples[0].answers_file(),
+            "part_one: 17\npart_two:\n##\n#.\n"
+        );
+        let error = super::examples("```answer part_two\n##\n```\n").unwrap_err();
+        assert_eq!(error.to_string(), "an answer block must follow an example");
+        let error = super::examples("```example\n1\n```\nprose\n```answer part_two\n##\n```\n")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "an answer block must follow an example");
+    }
+
+    #[test]
+    fn rejects_bad_answers() {
+        let error = examples("```example part1=3\n1\n```\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown part \"part1\", expected part_one or part_two"
+        );
+        let error = examples("```example part_one=3 part_one=4\n1\n```\n").unwrap_err();
+        assert_eq!(error.to_string(), "part_one is answered more than once");
+        let error = examples("```example part_two=\n1\n```\n").unwrap_err();
+        assert_eq!(error.to_string(), "the answer to part_two is empty");
+    }
+
+    #[test]
+    fn writes_fixtures_without_clobbering() {
+        let dir = std::env::temp_dir().join(format!("aoc-fixtures-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // a new day's empty placeholders are filled in
+        fs::write(dir.join("example1"), "").unwrap();
+        fs::write(dir.join("example1.answers"), "").unwrap();
+        let mut examples = examples(STATEMENT).unwrap();
+        examples[0].answers.clear();
+        let written = write_fixtures(&dir, &examples).unwrap();
+        assert_eq!(
+            written,
+            vec![dir.join("example1"), dir.join("example1.answers")]
+        );
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), "1\n2\n");
+        assert_eq!(fs::read_to_string(&written[1]).unwrap(), "");
+        // writing the same fixtures again is fine, but changing them is not
+        write_fixtures(&dir, &examples).unwrap();
+        examples[0].input = "3\n".to_string();
+        assert!(write_fixtures(&dir, &examples).is_err());
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), "1\n2\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_unclosed_fence() {
+        let error = examples("prose\n```example\n1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the fence opened on line 2 is never closed"
+        );
+    }
+
+    #[test]
+    fn renders_plain() {
+        let rendered = render(STATEMENT, 20, false).unwrap();
+        assert_eq!(
+            rendered,
+            "--- Day 1: Test ---
+Some prose with
+`code` in it.
+
+    not an example
+
+Example (part_one = 3, part_two = 12):
+    1
+    2
+"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+mod scaffold;
+mod watch;
+
+use aoc_core::statement;
+use clap::{Parser, Subcommand};
+use eyre::{Report, Result};
+use std::io::IsTerminal;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one part of a day against its puzzle input
+    Run {
+        #[arg(short, long)]
+        year: u16,
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long)]
+        part: u8,
+    },
+    /// Scaffold a new day's module, registration, and problem directory
+    New {
+        #[arg(short, long)]
+        year: u16,
+        #[arg(short, long)]
+        day: u8,
+    },
+    /// Print a day's puzzle statement
+    Show {
+        #[arg(short, long)]
+        year: u16,
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long, default_value_t = 80)]
+        width: usize,
+    },
+    /// Re-run a day's example tests and real input whenever its source or problem files change
+    Watch {
+        #[arg(short, long)]
+        year: u16,
+        #[arg(short, long)]
+        day: u8,
+        /// Only run this part against the real input, instead of both
+        #[arg(short, long)]
+        part: Option<u8>,
+    },
+    /// Extract the examples in a day's puzzle statement into test fixtures
+    Examples {
+        #[arg(short, long)]
+        year: u16,
+        #[arg(short, long)]
+        day: u8,
+    },
+}
+
+fn solve(year: u16, day: u8, part: u8) -> Result<()> {
+    match year {
+        2019 => aoc2019::solve(day, part),
+        2021 => aoc2021::solve(day, part),
+        2022 => aoc2022::solve(day, part),
+        year => Err(Report::msg(format!("there are no solutions for {year}"))),
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    match args.command {
+        Command::Run { year, day, part } => solve(year, day, part),
+        Command::New { year, day } => scaffold::new_day(year, day),
+        Command::Show { year, day, width } => {
+            let markdown = statement::read_statement(year, day)?;
+            let color = std::io::stdout().is_terminal();
+            print!("{}", statement::render(&markdown, width, color)?);
+            Ok(())
+        }
+        Command::Watch { year, day, part } => watch::watch(year, day, part),
+        Command::Examples { year, day } => {
+            for path in statement::extract_fixtures(year, day)? {
+                println!("Wrote {}", path.display());
+            }
+            Ok(())
+        }
+    }
+}
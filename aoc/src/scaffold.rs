@@ -0,0 +1,165 @@
+use eyre::{ContextCompat, Report, Result, WrapErr};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.template");
+
+/// The catch-all arm every year's `solve` ends with; new arms are inserted above it.
+const FALLTHROUGH_ARM: &str = "        (d, p) => not_implemented(YEAR, d, p),";
+
+fn module_name(day: u8) -> String {
+    format!("day{day:02}")
+}
+
+fn crate_src_dir(year: u16) -> PathBuf {
+    aoc_core::workspace_root()
+        .join(year.to_string())
+        .join("rust")
+        .join("src")
+}
+
+fn render_template(day: u8) -> String {
+    TEMPLATE.replace("{day}", &day.to_string())
+}
+
+/// Adds `pub mod dayNN;` to an `advent/mod.rs`, keeping the declarations sorted.
+fn register_module(mod_rs: &str, day: u8) -> Result<String> {
+    let declaration = format!("pub mod {};", module_name(day));
+    let mut lines: Vec<&str> = mod_rs.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Err(Report::msg(format!("{declaration} is already declared")));
+    }
+    let position = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod day") && *line > declaration.as_str())
+        .unwrap_or(lines.len());
+    lines.insert(position, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds both parts of a day to the year's `solve` match, just above the catch-all arm.
+fn register_arms(lib_rs: &str, day: u8) -> Result<String> {
+    let module = module_name(day);
+    if lib_rs.contains(&format!("advent::{module}::")) {
+        return Err(Report::msg(format!("{module} already has match arms")));
+    }
+    let arms = [(1, "part_one"), (2, "part_two")]
+        .map(|(part, solver)| {
+            format!(
+                "        ({day}, {part}) => run(YEAR, {day}, advent::{module}::parse, advent::{module}::{solver}),\n"
+            )
+        })
+        .concat();
+    let position = lib_rs
+        .find(FALLTHROUGH_ARM)
+        .wrap_err("could not find the not_implemented arm to insert before")?;
+    let mut registered = lib_rs.to_string();
+    registered.insert_str(position, &arms);
+    Ok(registered)
+}
+
+fn update(path: &Path, edit: impl Fn(&str) -> Result<String>) -> Result<()> {
+    let content =
+        fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+    let updated =
+        edit(&content).wrap_err_with(|| format!("failed to update {}", path.display()))?;
+    fs::write(path, updated).wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+/// Generates `dayNN.rs` for a year from the template, registers it, and creates its problem
+/// directory. Refuses to touch a day that already has a module.
+pub fn new_day(year: u16, day: u8) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(Report::msg(format!("day {day} is outside of 1..=25")));
+    }
+    let src = crate_src_dir(year);
+    let lib_rs = src.join("lib.rs");
+    if !lib_rs.exists() {
+        return Err(Report::msg(format!("there is no crate for {year}")));
+    }
+    let module = src.join("advent").join(format!("{}.rs", module_name(day)));
+    if module.exists() {
+        return Err(Report::msg(format!(
+            "{} already exists, refusing to overwrite it",
+            module.display()
+        )));
+    }
+    // Validate both registrations before writing anything, so a refusal leaves no partial day
+    let mod_rs = src.join("advent").join("mod.rs");
+    register_module(&fs::read_to_string(&mod_rs)?, day)?;
+    register_arms(&fs::read_to_string(&lib_rs)?, day)?;
+
+    fs::write(&module, render_template(day))?;
+    update(&mod_rs, |content| register_module(content, day))?;
+    update(&lib_rs, |content| register_arms(content, day))?;
+
+    let problem_dir = aoc_core::problem_dir(year, day);
+    fs::create_dir_all(&problem_dir)?;
+    let input = aoc_core::problem_input_path(year, day, Some(1));
+    if !input.exists() {
+        fs::write(&input, "")?;
+    }
+    // an example without answers checks nothing, but keeps the example tests from failing
+    let example = problem_dir.join("example1");
+    if !example.exists() {
+        fs::write(&example, "")?;
+        fs::write(example.with_extension("answers"), "")?;
+    }
+    println!("Created {}", module.display());
+    println!("Paste the puzzle input into {}", input.display());
+    println!(
+        "Paste the first example and its answers into {} and {}.answers",
+        example.display(),
+        example.display()
+    );
+    println!(
+        "Paste the puzzle statement into {}",
+        aoc_core::statement::problem_statement_path(year, day).display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_module_in_order() {
+        let mod_rs = "pub mod day01;\npub mod day03;\n";
+        let registered = register_module(mod_rs, 2).unwrap();
+        assert_eq!(
+            registered,
+            "pub mod day01;\npub mod day02;\npub mod day03;\n"
+        );
+        let registered = register_module(mod_rs, 4).unwrap();
+        assert_eq!(
+            registered,
+            "pub mod day01;\npub mod day03;\npub mod day04;\n"
+        );
+        assert!(register_module(mod_rs, 3).is_err());
+    }
+
+    #[test]
+    fn registers_arms_above_fallthrough() {
+        let lib_rs = format!("    match (day, part) {{\n{FALLTHROUGH_ARM}\n    }}\n");
+        let registered = register_arms(&lib_rs, 7).unwrap();
+        let expected = format!(
+            "    match (day, part) {{
+        (7, 1) => run(YEAR, 7, advent::day07::parse, advent::day07::part_one),
+        (7, 2) => run(YEAR, 7, advent::day07::parse, advent::day07::part_two),
+{FALLTHROUGH_ARM}
+    }}
+"
+        );
+        assert_eq!(registered, expected);
+        assert!(register_arms(&registered, 7).is_err());
+    }
+
+    #[test]
+    fn template_mentions_day() {
+        let rendered = render_template(9);
+        assert!(rendered.contains("day 9 part one"));
+        assert!(rendered.contains("aoc_core::example_tests!(crate::YEAR, 9);"));
+        assert!(!rendered.contains("{day}"));
+    }
+}
@@ -0,0 +1,274 @@
+use eyre::{Report, Result, WrapErr};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Editors tend to write a file in several steps, so changes are collected for this long
+/// before re-running anything.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+#[derive(Debug, PartialEq, Eq)]
+struct TestSummary {
+    passed: usize,
+    failed: Vec<String>,
+}
+
+/// Pulls the passing count and the names of failing tests out of `cargo test` output.
+fn summarise_tests(output: &str) -> TestSummary {
+    let mut passed = 0;
+    let mut failed = Vec::new();
+    for line in output.lines() {
+        if let Some(name) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+        {
+            failed.push(name.to_string());
+        } else if line.starts_with("test ") && line.ends_with(" ... ok") {
+            passed += 1;
+        }
+    }
+    TestSummary { passed, failed }
+}
+
+fn describe_answer(answer: &str, previous: Option<&String>) -> String {
+    match previous {
+        None => answer.to_string(),
+        Some(previous) if previous == answer => format!("{answer} (unchanged)"),
+        Some(previous) => format!("{answer} (was {previous})"),
+    }
+}
+
+fn cargo() -> Command {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    command.current_dir(aoc_core::workspace_root());
+    command
+}
+
+/// Runs only the tests generated for a day's examples, which sit either directly in its module or
+/// in its `tests` module. Other tests, such as differential ones, can be slow and can write
+/// counterexamples into the watched directory.
+fn run_tests(year: u16, day: u8) -> Result<TestSummary> {
+    let output = cargo()
+        .args(["test", "-p", &format!("aoc{year}"), "--lib", "--"])
+        .arg(format!("advent::day{day:02}::examples::"))
+        .arg(format!("advent::day{day:02}::tests::examples::"))
+        .output()
+        .wrap_err("failed to run cargo test")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() && !stdout.contains("test result") {
+        // the crate did not build, so there is no test output to summarise
+        return Err(Report::msg(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+    Ok(summarise_tests(&stdout))
+}
+
+/// Runs one part against the real input, returning its answer and the runner's timings.
+fn run_part(year: u16, day: u8, part: u8) -> Result<(String, String)> {
+    let output = cargo()
+        .args(["run", "--quiet", "--release", "-p", "aoc", "--", "run"])
+        .args(["-y", &year.to_string(), "-d", &day.to_string()])
+        .args(["-p", &part.to_string()])
+        .output()
+        .wrap_err("failed to run cargo run")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().find(|line| !line.is_empty()).unwrap_or("");
+        return Err(Report::msg(reason.to_string()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // the runner prints the answer, then a final line of timings
+    let lines: Vec<&str> = stdout.lines().collect();
+    match lines.split_last() {
+        Some((timings, answer)) => Ok((answer.join("\n"), timings.to_string())),
+        None => Err(Report::msg("the runner printed nothing")),
+    }
+}
+
+fn report(year: u16, day: u8, parts: &[u8], previous: &mut BTreeMap<u8, String>) {
+    println!("--- {year} day {day} ---");
+    match run_tests(year, day) {
+        Ok(summary) if summary.failed.is_empty() => {
+            println!("tests: {} passed", summary.passed)
+        }
+        Ok(summary) => {
+            println!(
+                "tests: {} passed, {} FAILED",
+                summary.passed,
+                summary.failed.len()
+            );
+            for name in summary.failed {
+                println!("  {name}");
+            }
+            // failing examples make the real answers meaningless
+            return;
+        }
+        Err(err) => {
+            println!("build failed:\n{err}");
+            return;
+        }
+    }
+    for &part in parts {
+        match run_part(year, day, part) {
+            Ok((answer, timings)) => {
+                let description = describe_answer(&answer, previous.get(&part));
+                println!("part {part}: {description} [{timings}]");
+                previous.insert(part, answer);
+            }
+            Err(err) => println!("part {part}: error: {err}"),
+        }
+    }
+}
+
+/// Whether a change is worth re-running for: one to the day's source, or to its problem files
+/// other than counterexamples. Counterexamples are only ever written by tests, so they are left
+/// out rather than set off another run.
+fn is_relevant(event: &notify::Event, source: &Path, problems: &Path) -> bool {
+    event.paths.iter().any(|path| {
+        path == source
+            || (path.starts_with(problems)
+                && !path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("counterexample")))
+    })
+}
+
+/// Watches the directory holding `source` rather than the file itself, as editors that save by
+/// renaming a new file over the old one would otherwise leave the watch on a deleted file.
+fn watch_files(
+    source: &Path,
+    problems: &Path,
+    sender: mpsc::Sender<notify::Result<notify::Event>>,
+) -> Result<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(sender)?;
+    let advent = source.parent().unwrap_or(source);
+    watcher
+        .watch(advent, RecursiveMode::NonRecursive)
+        .wrap_err_with(|| format!("failed to watch {}", advent.display()))?;
+    watcher
+        .watch(problems, RecursiveMode::Recursive)
+        .wrap_err_with(|| format!("failed to watch {}", problems.display()))?;
+    Ok(watcher)
+}
+
+/// Re-runs a day's example tests and then its real input whenever its source or problem files change.
+pub fn watch(year: u16, day: u8, part: Option<u8>) -> Result<()> {
+    let source: PathBuf = aoc_core::workspace_root()
+        .join(year.to_string())
+        .join("rust")
+        .join("src")
+        .join("advent")
+        .join(format!("day{day:02}.rs"));
+    let problems = aoc_core::problem_dir(year, day);
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+
+    let (sender, receiver) = mpsc::channel();
+    let _watcher = watch_files(&source, &problems, sender)?;
+
+    let mut previous = BTreeMap::new();
+    report(year, day, &parts, &mut previous);
+    while let Ok(event) = receiver.recv() {
+        if !is_relevant(&event?, &source, &problems) {
+            continue;
+        }
+        while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+        report(year, day, &parts, &mut previous);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarises_test_output() {
+        let output = "
+running 3 tests
+test advent::day17::tests::examples::part_one_examples ... ok
+test advent::day17::tests::examples::part_two_examples ... FAILED
+test advent::day17::tests::cycle ... ok
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 40 filtered out
+";
+        assert_eq!(
+            summarise_tests(output),
+            TestSummary {
+                passed: 2,
+                failed: vec!["advent::day17::tests::examples::part_two_examples".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_counterexamples_and_other_days() {
+        let source = PathBuf::from("2022/rust/src/advent/day16.rs");
+        let problems = PathBuf::from("2022/problems/day16");
+        let relevant = |path: PathBuf| {
+            is_relevant(&notify::Event::default().add_path(path), &source, &problems)
+        };
+        assert!(relevant(problems.join("example2")));
+        assert!(relevant(problems.join("input1")));
+        assert!(relevant(source.clone()));
+        assert!(!relevant(problems.join("counterexample1")));
+        assert!(!relevant(source.with_file_name("day17.rs")));
+    }
+
+    /// Whether a relevant event arrives before the watch goes quiet.
+    fn saw_relevant(
+        receiver: &mpsc::Receiver<notify::Result<notify::Event>>,
+        source: &Path,
+        problems: &Path,
+    ) -> bool {
+        let mut relevant = false;
+        while let Ok(event) = receiver.recv_timeout(Duration::from_secs(1)) {
+            relevant |= is_relevant(&event.unwrap(), source, problems);
+        }
+        relevant
+    }
+
+    #[test]
+    fn sees_saves_that_rename_over_the_source() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let advent = dir.join("advent");
+        let problems = dir.join("problems");
+        std::fs::create_dir_all(&advent).unwrap();
+        std::fs::create_dir_all(&problems).unwrap();
+        let source = advent.join("day16.rs");
+        std::fs::write(&source, "// first\n").unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let _watcher = watch_files(&source, &problems, sender).unwrap();
+        // every save replaces the file, so a watch on the file itself would only see the first
+        for save in 0..3 {
+            let temporary = advent.join("day16.rs.tmp");
+            std::fs::write(&temporary, format!("// save {save}\n")).unwrap();
+            std::fs::rename(&temporary, &source).unwrap();
+            assert!(
+                saw_relevant(&receiver, &source, &problems),
+                "missed save {save}"
+            );
+        }
+        std::fs::write(advent.join("day17.rs"), "// another day\n").unwrap();
+        assert!(!saw_relevant(&receiver, &source, &problems));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        assert_eq!(describe_answer("3068", None), "3068");
+        assert_eq!(
+            describe_answer("3068", Some(&"3068".to_string())),
+            "3068 (unchanged)"
+        );
+        assert_eq!(
+            describe_answer("3070", Some(&"3068".to_string())),
+            "3070 (was 3068)"
+        );
+    }
+}